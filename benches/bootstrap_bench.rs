@@ -1,3 +1,6 @@
+#![allow(missing_docs, unused_crate_dependencies)]
+#![allow(clippy::lint_groups_priority, clippy::wildcard_imports, clippy::cast_precision_loss, clippy::semicolon_if_nothing_returned)]
+
 use rand::prelude::thread_rng;
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand::prelude::*; // Provides thread_rng()
@@ -8,7 +11,7 @@ use rand::SeedableRng;
 const SAMPLE_SIZE: usize = 1_000;      // Fixed size for tests 2-4
 const RESAMPLES: usize = 10_000;       // Bootstrap iterations
 
-fn xrng() -> impl rand::Rng {
+fn xrng() -> Xoshiro256PlusPlus {
     <Xoshiro256PlusPlus as SeedableRng>::seed_from_u64(thread_rng().next_u64())
 }
 
@@ -95,7 +98,7 @@ fn bench_your_pattern(c: &mut Criterion) {
     // Pattern A: thread_rng
     c.bench_function("pattern/thread_rng", |b| {
         b.iter(|| {
-            let estimated: Sample<f32> = sample::Bootstrap::new(xrng())
+            let estimated: Sample<f32> = Bootstrap::new(xrng())
                 .re(&sample)
                 .take(RESAMPLES)
                 .map(|res| statistic.compute(&res))
@@ -107,7 +110,25 @@ fn bench_your_pattern(c: &mut Criterion) {
     // Pattern B: xoshiro
     c.bench_function("pattern/xoshiro", |b| {
         b.iter(|| {
-            let estimated: Sample<f32> = sample::Bootstrap::new(xrng())
+            let estimated: Sample<f32> = Bootstrap::new(xrng())
+                .re(&sample)
+                .take(RESAMPLES)
+                .map(|res| statistic.compute(&res))
+                .collect();
+            black_box(estimated)
+        })
+    });
+}
+
+/// 6. OWNED VS LENT REPLICATES: one allocation per replicate vs one buffer
+fn bench_lending(c: &mut Criterion) {
+    let sample: Sample<f32> = (0..SAMPLE_SIZE).map(|i| (i % 100) as f32).collect();
+    let statistic = Variance { ddof: 1 };
+    let mut group = c.benchmark_group("bootstrap/replicates");
+
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            let estimated: Sample<f32> = Bootstrap::new(xrng())
                 .re(&sample)
                 .take(RESAMPLES)
                 .map(|res| statistic.compute(&res))
@@ -115,6 +136,49 @@ fn bench_your_pattern(c: &mut Criterion) {
             black_box(estimated)
         })
     });
+
+    group.bench_function("lent", |b| {
+        b.iter(|| {
+            let mut estimated: Vec<f32> = Vec::with_capacity(RESAMPLES);
+            Bootstrap::new(xrng()).for_each_resample(&sample, RESAMPLES, |res| {
+                estimated.push(statistic.compute(res));
+            });
+            black_box(estimated)
+        })
+    });
+
+    let se = SE::new(Mean, Bootstrap::new(xrng()), 1_000);
+    group.bench_function("se_owned", |b| {
+        b.iter(|| black_box::<f32>(se.compute(black_box(&sample))))
+    });
+    group.bench_function("se_lent", |b| {
+        b.iter(|| black_box::<f32>(se.compute_buffered(black_box(&sample))))
+    });
+
+    group.finish();
+}
+
+/// 7. VARIANCE REDUCTION: plain vs balanced vs antithetic SE at the same B
+fn bench_variance_reduction(c: &mut Criterion) {
+    let sample: Sample<f32> = (0..SAMPLE_SIZE).map(|i| (i % 100) as f32).collect();
+    let mut group = c.benchmark_group("bootstrap/variance_reduction");
+
+    let plain = SE::new(Mean, Bootstrap::new(xrng()), 1_000);
+    group.bench_function("plain", |b| {
+        b.iter(|| black_box::<f32>(plain.compute(black_box(&sample))))
+    });
+
+    let balanced = SE::new(Mean, BalancedBootstrap::new(xrng(), 1_000), 1_000);
+    group.bench_function("balanced", |b| {
+        b.iter(|| black_box::<f32>(balanced.compute(black_box(&sample))))
+    });
+
+    let antithetic = SE::new(Mean, AntitheticBootstrap::new(xrng()), 1_000);
+    group.bench_function("antithetic", |b| {
+        b.iter(|| black_box::<f32>(antithetic.compute(black_box(&sample))))
+    });
+
+    group.finish();
 }
 
 criterion_group!(
@@ -123,6 +187,8 @@ criterion_group!(
     bench_se_jackknife,
     // bench_bootstrap_thread_rng,
     // bench_bootstrap_xoshiro,
-    bench_your_pattern
+    bench_your_pattern,
+    bench_lending,
+    bench_variance_reduction
 );
 criterion_main!(benches);
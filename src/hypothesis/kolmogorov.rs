@@ -1,5 +1,4 @@
 use num_traits::{Float, FromPrimitive, ToPrimitive};
-use rand::thread_rng;
 use statrs::distribution::{Normal, ContinuousCDF};
 
 use crate::{CDF, Statistic, TestResult}; // Assuming TestResult is defined in crate root
@@ -1,13 +1,19 @@
 use num_traits::{Float, FromPrimitive};
-use rand::thread_rng;
+use rand::{Rng, SeedableRng, thread_rng};
+use rand::rngs::StdRng;
 use statrs::distribution::{Normal, ContinuousCDF};
 
-use crate::{CDF, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic};
+use crate::{CDF, ExactFlipper, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic};
+#[cfg(feature = "rayon")]
+use crate::RePar;
+use super::exact_p_value;
 
 /// Permutation test for the hypothesis about the population mean.
 ///
 /// Tests the null hypothesis: `H₀: μ = μ₀` without assuming normality of the distribution.
 /// Uses the sign-flipping (random sign inversion) method on centered data.
+/// When `2ⁿ ≤ n_permutations` every flip pattern is enumerated with
+/// [`ExactFlipper`](crate::ExactFlipper) and the p-value is exact.
 ///
 /// # Statistical assumptions
 /// - **Assumes**: i.i.d. sample, existence of the population mean
@@ -16,10 +22,10 @@ use crate::{CDF, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic};
 ///
 /// # Example
 /// ```rust
-/// use your_crate::MeanTest;
+/// use zima::{MeanTest, Statistic};
 ///
 /// let data = vec![0.5, -1.2, 0.8, 1.5, -0.3];
-/// let test = MeanTest::<f64>::zero(0.01); // accuracy ±0.01
+/// let test = MeanTest::<f64>::zero(0.01).with_seed(42); // accuracy ±0.01, reproducible
 /// let result = test.compute(&data);
 ///
 /// println!("p-value: {:.4}", result.p_value);
@@ -28,6 +34,8 @@ use crate::{CDF, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic};
 pub struct MeanTest<F> {
     pub null_mean: F,
     pub n_permutations: usize,
+    /// Seed of the sign flips; `None` draws a fresh one from `thread_rng`
+    pub seed: Option<u64>,
 }
 
 /// Result of the permutation test.
@@ -35,7 +43,8 @@ pub struct MeanTest<F> {
 pub struct TestResult<F: Float> {
     /// Observed test statistic (mean of centered data).
     pub observed_statistic: F,
-    /// Estimated p-value with continuity correction.
+    /// Estimated p-value with continuity correction, or the exact one when
+    /// all 2ⁿ sign flips were enumerated.
     pub p_value: F,
 }
 
@@ -56,15 +65,24 @@ where
             };
         }
 
-        let centered: Sample<F> = data_slice
-            .iter()
-            .map(|&x| x - self.null_mean)
-            .collect();
-
+        let centered = self.center(data_slice);
         let observed_stat = Mean.compute(&centered);
         let observed_abs = observed_stat.abs();
 
-        let flipper = Flipper::sign(thread_rng());
+        // Exact p-value when all 2ⁿ flips are cheaper than the requested sampling
+        if let Some(patterns) = self.exact_patterns(n) {
+            let extreme_count = ExactFlipper::sign()
+                .re(&centered)
+                .filter(|resample| Mean.compute(resample).abs() >= observed_abs)
+                .count();
+
+            return TestResult {
+                p_value: exact_p_value(extreme_count, patterns),
+                observed_statistic: observed_stat,
+            };
+        }
+
+        let flipper = Flipper::sign(self.rng());
         let permuted_stats: Sample<F> = flipper
             .re(&centered)
             .map(|resample| Mean.compute(&resample))
@@ -77,11 +95,91 @@ where
             .filter(|&&stat| stat.abs() >= observed_abs)
             .count();
 
-        let p_value = F::from(extreme_count + 1).expect("extreme_count + 1 fits in float")
-            / F::from(self.n_permutations + 1).expect("n_permutations + 1 fits in float");
+        TestResult {
+            p_value: self.p_value(extreme_count),
+            observed_statistic: observed_stat,
+        }
+    }
+}
+
+impl<F: Float + FromPrimitive> MeanTest<F> {
+    /// Data centered at the hypothesized mean.
+    fn center(&self, data: &[F]) -> Sample<F> {
+        data.iter().map(|&x| x - self.null_mean).collect()
+    }
+
+    /// p-value with continuity correction: `(extreme + 1) / (B + 1)`.
+    fn p_value(&self, extreme_count: usize) -> F {
+        F::from(extreme_count + 1).expect("extreme_count + 1 fits in float")
+            / F::from(self.n_permutations + 1).expect("n_permutations + 1 fits in float")
+    }
+
+    /// Number of sign patterns `2ⁿ` if enumerating them all costs no more than
+    /// `n_permutations` random draws.
+    fn exact_patterns(&self, n: usize) -> Option<usize> {
+        let patterns = ExactFlipper::<SignBitFlip>::patterns(n);
+        (patterns <= self.n_permutations).then_some(patterns)
+    }
+
+    /// Fix the seed of the sign flips, making `compute` reproducible.
+    ///
+    /// Results are bit-identical for a given seed and `rand` version.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// RNG of the sign flips.
+    fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed.unwrap_or_else(|| thread_rng().r#gen()))
+    }
+
+    /// Parallel counterpart of `compute`.
+    ///
+    /// Sign flips come from the same per-replicate streams of the seed (see
+    /// `RePar`), so the p-value equals that of `compute` and does not depend
+    /// on the number of threads.
+    #[cfg(feature = "rayon")]
+    pub fn compute_par<D>(&self, data: &D) -> TestResult<F>
+    where
+        D: AsRef<[F]>,
+        F: Send + Sync,
+        SignBitFlip: Flip<F>,
+    {
+        use rayon::prelude::*;
+
+        let data_slice = data.as_ref();
+        if data_slice.is_empty() {
+            return TestResult {
+                p_value: F::one(),
+                observed_statistic: F::zero(),
+            };
+        }
+
+        let centered = self.center(data_slice);
+        let observed_stat = Mean.compute(&centered);
+        let observed_abs = observed_stat.abs();
+
+        if let Some(patterns) = self.exact_patterns(data_slice.len()) {
+            let extreme_count = ExactFlipper::sign()
+                .re_par(&centered)
+                .filter(|resample| Mean.compute(resample).abs() >= observed_abs)
+                .count();
+
+            return TestResult {
+                p_value: exact_p_value(extreme_count, patterns),
+                observed_statistic: observed_stat,
+            };
+        }
+
+        let extreme_count = Flipper::sign(self.rng())
+            .re_par(&centered)
+            .take(self.n_permutations)
+            .filter(|resample| Mean.compute(resample).abs() >= observed_abs)
+            .count();
 
         TestResult {
-            p_value,
+            p_value: self.p_value(extreme_count),
             observed_statistic: observed_stat,
         }
     }
@@ -101,6 +199,7 @@ impl<F: Float + FromPrimitive> MeanTest<F> {
         Self {
             null_mean,
             n_permutations,
+            seed: None,
         }
     }
 
@@ -118,8 +217,8 @@ impl<F: Float + FromPrimitive> MeanTest<F> {
     /// 1. This controls **sampling error** from approximating the permutation
     ///    distribution, NOT the inherent discreteness of the exact permutation test.
     ///    The exact test has only 2ⁿ unique sign-flipping configurations.
-    /// 2. For small samples (n < 15), the discreteness dominates — consider
-    ///    exhaustive enumeration of all 2ⁿ permutations instead of sampling.
+    /// 2. For small samples the discreteness dominates; whenever 2ⁿ ≤ n_permutations
+    ///    the test enumerates all flips and reports the exact p-value instead.
     /// 3. The guarantee is conservative: actual accuracy is substantially better
     ///    when the true p-value is far from 0.5 (e.g., p < 0.1 or p > 0.9).
     /// 4. "Accuracy ±0.01" refers to estimation error of p-value, NOT error in
@@ -171,6 +270,7 @@ impl<F: Float + FromPrimitive> MeanTest<F> {
         Self {
             null_mean,
             n_permutations,
+            seed: None,
         }
     }
 
@@ -189,6 +289,7 @@ impl<F: Float + FromPrimitive> MeanTest<F> {
     ///
     /// # Example
     /// ```rust
+    /// # use zima::MeanTest;
     /// let test = MeanTest::<f64>::zero(0.01); // ±1% accuracy
     /// ```
     pub fn zero(accuracy: f64) -> Self
@@ -198,3 +299,28 @@ impl<F: Float + FromPrimitive> MeanTest<F> {
         Self::from_absolute_accuracy(F::zero(), accuracy, 0.95)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_samples_get_exact_p_values() {
+        // All 2⁵ flips: only the identity and the full flip reach |mean| = 3
+        let data = vec![1.0_f64, 2.0, 3.0, 4.0, 5.0];
+        let test = MeanTest::new(0.0, 1000);
+        let result = test.compute(&data);
+        assert!((result.p_value - 2.0 / 32.0).abs() < 1e-12);
+
+        // Deterministic, unlike the sampled p-value
+        assert_eq!(test.compute(&data), result);
+
+        // Too few permutations requested: falls back to sampling with correction
+        let sampled = MeanTest::new(0.0, 16).compute(&data);
+        assert!(sampled.p_value >= 1.0 / 17.0);
+
+        // A seed makes the sampled p-value reproducible too
+        let seeded = MeanTest::new(0.5, 16).with_seed(3);
+        assert_eq!(seeded.compute(&data), seeded.compute(&data));
+    }
+}
@@ -2,8 +2,18 @@ mod dagostino;
 mod mean;
 mod variance;
 mod kolmogorov;
+mod permutation;
 
 pub use dagostino::{DagostinoPearson, DagostinoPearsonResult};
 pub use mean::*;
 pub use variance::*;
 pub use kolmogorov::*;
+pub use permutation::PermutationTest;
+
+use num_traits::{Float, FromPrimitive};
+
+/// Exact p-value over the full flip group: `extreme / 2ⁿ` (the identity is counted).
+fn exact_p_value<F: Float + FromPrimitive>(extreme_count: usize, patterns: usize) -> F {
+    F::from(extreme_count).expect("extreme_count fits in float")
+        / F::from(patterns).expect("patterns fits in float")
+}
@@ -1,8 +1,12 @@
 use num_traits::{Float, FromPrimitive};
-use rand::thread_rng;
+use rand::{Rng, SeedableRng, thread_rng};
+use rand::rngs::StdRng;
 use statrs::distribution::{Normal, ContinuousCDF};
 
-use crate::{CDF, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic, Variance};
+use crate::{CDF, ExactFlipper, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic, Variance};
+#[cfg(feature = "rayon")]
+use crate::RePar;
+use super::exact_p_value;
 
 /// Permutation test for the hypothesis about the population variance.
 ///
@@ -14,6 +18,7 @@ use crate::{CDF, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic, Varian
 /// 2. Under H₀, signs of centered observations are exchangeable
 /// 3. Random sign flipping preserves the joint distribution under H₀
 /// 4. Compare observed variance deviation `|s² - σ₀²|` with permuted distribution
+/// 5. When `2ⁿ ≤ n_permutations`, enumerate every flip pattern for an exact p-value
 ///
 /// # Statistical assumptions
 /// - **Assumes**: i.i.d. sample, existence of population variance
@@ -22,7 +27,7 @@ use crate::{CDF, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic, Varian
 ///
 /// # Example
 /// ```rust
-/// use your_crate::VarianceTest;
+/// use zima::{Statistic, VarianceTest};
 ///
 /// let data = vec![0.5, -1.2, 0.8, 1.5, -0.3];
 /// let test = VarianceTest::<f64>::unit(0.01); // test σ² = 1 with ±0.01 accuracy
@@ -35,6 +40,8 @@ use crate::{CDF, Flip, Flipper, Mean, Re, Sample, SignBitFlip, Statistic, Varian
 pub struct VarianceTest<F> {
     pub null_variance: F,
     pub n_permutations: usize,
+    /// Seed of the sign flips; `None` draws a fresh one from `thread_rng`
+    pub seed: Option<u64>,
 }
 
 /// Result of the permutation test for variance.
@@ -42,7 +49,8 @@ pub struct VarianceTest<F> {
 pub struct TestResult<F: Float> {
     /// Observed test statistic: sample variance (unbiased estimator).
     pub observed_statistic: F,
-    /// Estimated p-value with continuity correction.
+    /// Estimated p-value with continuity correction, or the exact one when
+    /// all 2ⁿ sign flips were enumerated.
     pub p_value: F,
 }
 
@@ -64,25 +72,31 @@ where
             };
         }
 
-        let sample_mean = Mean.compute(&data);
-        let centered: Sample<F> = data_slice
-            .iter()
-            .map(|&x| x - sample_mean)
-            .collect();
+        let centered = Self::center(data_slice);
 
         // Step 2: Compute observed unbiased variance
         let observed_var = Variance::default().compute(&centered); // Σ(x_i - x̄)² / (n-1)
         let observed_deviation = (observed_var - self.null_variance).abs();
 
+        // Exact p-value when all 2ⁿ flips are cheaper than the requested sampling
+        if let Some(patterns) = self.exact_patterns(n) {
+            let extreme_count = ExactFlipper::sign()
+                .re(&centered)
+                .filter(|resample| self.deviation(resample) >= observed_deviation)
+                .count();
+
+            return TestResult {
+                p_value: exact_p_value(extreme_count, patterns),
+                observed_statistic: observed_var,
+            };
+        }
+
         // Step 3: Generate permuted statistics via sign flipping
         // Sign flipping preserves variance distribution under H₀
-        let flipper = Flipper::sign(thread_rng());
+        let flipper = Flipper::sign(self.rng());
         let permuted_deviations: Sample<F> = flipper
             .re(&centered)
-            .map(|resample| {
-                let var = Variance::default().compute(&resample);
-                (var - self.null_variance).abs()
-            })
+            .map(|resample| self.deviation(&resample))
             .take(self.n_permutations)
             .collect();
 
@@ -93,12 +107,97 @@ where
             .filter(|&&dev| dev >= observed_deviation)
             .count();
 
-        // Continuity correction for discrete permutation distribution
-        let p_value = F::from(extreme_count + 1).expect("extreme_count + 1 fits in float")
-            / F::from(self.n_permutations + 1).expect("n_permutations + 1 fits in float");
+        TestResult {
+            p_value: self.p_value(extreme_count),
+            observed_statistic: observed_var,
+        }
+    }
+}
+
+impl<F: Float + FromPrimitive> VarianceTest<F> {
+    /// Data centered at the sample mean.
+    fn center(data: &[F]) -> Sample<F> {
+        let sample_mean = Mean.compute(&data);
+        data.iter().map(|&x| x - sample_mean).collect()
+    }
+
+    /// Deviation of the resample variance from the hypothesized one: `|s² - σ₀²|`.
+    fn deviation(&self, resample: &Sample<F>) -> F {
+        (Variance::default().compute(resample) - self.null_variance).abs()
+    }
+
+    /// Continuity correction for discrete permutation distribution
+    fn p_value(&self, extreme_count: usize) -> F {
+        F::from(extreme_count + 1).expect("extreme_count + 1 fits in float")
+            / F::from(self.n_permutations + 1).expect("n_permutations + 1 fits in float")
+    }
+
+    /// Number of sign patterns `2ⁿ` if enumerating them all costs no more than
+    /// `n_permutations` random draws.
+    fn exact_patterns(&self, n: usize) -> Option<usize> {
+        let patterns = ExactFlipper::<SignBitFlip>::patterns(n);
+        (patterns <= self.n_permutations).then_some(patterns)
+    }
+
+    /// Fix the seed of the sign flips, making `compute` reproducible.
+    ///
+    /// Results are bit-identical for a given seed and `rand` version.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// RNG of the sign flips.
+    fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed.unwrap_or_else(|| thread_rng().r#gen()))
+    }
+
+    /// Parallel counterpart of `compute`.
+    ///
+    /// Sign flips come from the same per-replicate streams of the seed (see
+    /// `RePar`), so the p-value equals that of `compute` and does not depend
+    /// on the number of threads.
+    #[cfg(feature = "rayon")]
+    pub fn compute_par<D>(&self, data: &D) -> TestResult<F>
+    where
+        D: AsRef<[F]>,
+        F: Send + Sync,
+        SignBitFlip: Flip<F>,
+    {
+        use rayon::prelude::*;
+
+        let data_slice = data.as_ref();
+        if data_slice.len() < 2 {
+            return TestResult {
+                p_value: F::one(),
+                observed_statistic: F::nan(),
+            };
+        }
+
+        let centered = Self::center(data_slice);
+        let observed_var = Variance::default().compute(&centered);
+        let observed_deviation = (observed_var - self.null_variance).abs();
+
+        if let Some(patterns) = self.exact_patterns(data_slice.len()) {
+            let extreme_count = ExactFlipper::sign()
+                .re_par(&centered)
+                .filter(|resample| self.deviation(resample) >= observed_deviation)
+                .count();
+
+            return TestResult {
+                p_value: exact_p_value(extreme_count, patterns),
+                observed_statistic: observed_var,
+            };
+        }
+
+        let extreme_count = Flipper::sign(self.rng())
+            .re_par(&centered)
+            .take(self.n_permutations)
+            .filter(|resample| self.deviation(resample) >= observed_deviation)
+            .count();
 
         TestResult {
-            p_value,
+            p_value: self.p_value(extreme_count),
             observed_statistic: observed_var,
         }
     }
@@ -121,6 +220,7 @@ impl<F: Float + FromPrimitive> VarianceTest<F> {
         Self {
             null_variance,
             n_permutations,
+            seed: None,
         }
     }
 
@@ -137,8 +237,8 @@ impl<F: Float + FromPrimitive> VarianceTest<F> {
     /// # Important caveats
     /// 1. This controls **sampling error** from approximating the permutation
     ///    distribution, NOT the inherent discreteness of the exact permutation test.
-    /// 2. For small samples (n < 15), the exact permutation distribution has only
-    ///    2ⁿ unique values — consider exhaustive enumeration instead.
+    /// 2. For small samples the exact permutation distribution has only 2ⁿ values;
+    ///    whenever 2ⁿ ≤ n_permutations the test enumerates them all instead.
     /// 3. The guarantee is conservative: for p-values far from 0.5, the actual
     ///    accuracy will be substantially better than requested.
     ///
@@ -188,6 +288,7 @@ impl<F: Float + FromPrimitive> VarianceTest<F> {
         Self {
             null_variance,
             n_permutations,
+            seed: None,
         }
     }
 
@@ -206,6 +307,7 @@ impl<F: Float + FromPrimitive> VarianceTest<F> {
     ///
     /// # Example
     /// ```rust
+    /// # use zima::VarianceTest;
     /// let test = VarianceTest::<f64>::unit(0.01); // test σ² = 1 with ±1% accuracy
     /// ```
     pub fn unit(accuracy: f64) -> Self
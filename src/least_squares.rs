@@ -1,3 +1,120 @@
+use num_traits::{Float, FromPrimitive};
+use crate::{Paired, Statistic};
+
+/// Straight line `y = intercept + slope·x`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line<T> {
+    pub intercept: T,
+    pub slope: T,
+}
+
+impl<T: Float> Line<T> {
+    /// Fitted value at `x`
+    #[inline]
+    pub fn predict(&self, x: T) -> T {
+        self.intercept + self.slope * x
+    }
+
+    /// Residuals `yᵢ - ŷᵢ` of the pairs around the line
+    pub fn residuals(&self, data: &Paired<T>) -> Vec<T> {
+        data.iter().map(|(&x, &y)| y - self.predict(x)).collect()
+    }
+}
+
+/// Ordinary least-squares line through paired data.
+///
+/// ```text
+/// β̂ = Σ(xᵢ - x̄)(yᵢ - ȳ) / Σ(xᵢ - x̄)²,   α̂ = ȳ - β̂·x̄
+/// ```
+/// Both coefficients are NaN for fewer than two pairs or a constant regressor.
+/// Resample the pairs with `PairsBootstrap` or `ResidualBootstrap` to get
+/// standard errors and intervals without assuming normal errors.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeastSquares;
+
+impl<T: Float + FromPrimitive> Statistic<Paired<T>, Line<T>> for LeastSquares {
+    fn compute(&self, data: &Paired<T>) -> Line<T> {
+        let nan = Line {
+            intercept: T::nan(),
+            slope: T::nan(),
+        };
+        if data.len() < 2 {
+            return nan;
+        }
+
+        let n = T::from_usize(data.len()).expect("sample size fits in float");
+        let (sum_x, sum_y) = data
+            .iter()
+            .fold((T::zero(), T::zero()), |(sx, sy), (&x, &y)| (sx + x, sy + y));
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+
+        let (sxx, sxy) = data.iter().fold((T::zero(), T::zero()), |(sxx, sxy), (&x, &y)| {
+            let dx = x - mean_x;
+            (sxx + dx * dx, sxy + dx * (y - mean_y))
+        });
+        if sxx <= T::zero() {
+            return nan;
+        }
+
+        let slope = sxy / sxx;
+        Line {
+            intercept: mean_y - slope * mean_x,
+            slope,
+        }
+    }
+}
+
+/// Least-squares slope `β̂`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Slope;
+
+impl<T: Float + FromPrimitive> Statistic<Paired<T>, T> for Slope {
+    fn compute(&self, data: &Paired<T>) -> T {
+        LeastSquares.compute(data).slope
+    }
+}
+
+/// Least-squares intercept `α̂`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Intercept;
+
+impl<T: Float + FromPrimitive> Statistic<Paired<T>, T> for Intercept {
+    fn compute(&self, data: &Paired<T>) -> T {
+        LeastSquares.compute(data).intercept
+    }
+}
+
+/// Pearson correlation `r = Sxy / √(Sxx·Syy)` of the pairs.
+///
+/// NaN for fewer than two pairs or a constant coordinate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Correlation;
+
+impl<T: Float + FromPrimitive> Statistic<Paired<T>, T> for Correlation {
+    fn compute(&self, data: &Paired<T>) -> T {
+        if data.len() < 2 {
+            return T::nan();
+        }
+
+        let n = T::from_usize(data.len()).expect("sample size fits in float");
+        let (sum_x, sum_y) = data
+            .iter()
+            .fold((T::zero(), T::zero()), |(sx, sy), (&x, &y)| (sx + x, sy + y));
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+
+        let (sxx, syy, sxy) = data
+            .iter()
+            .fold((T::zero(), T::zero(), T::zero()), |(sxx, syy, sxy), (&x, &y)| {
+                let (dx, dy) = (x - mean_x, y - mean_y);
+                (sxx + dx * dx, syy + dy * dy, sxy + dx * dy)
+            });
+        if sxx <= T::zero() || syy <= T::zero() {
+            return T::nan();
+        }
+        sxy / (sxx * syy).sqrt()
+    }
+}
+
 // use csv::Trim;
 // use num_traits::{Float, real::Real};
 
@@ -54,3 +171,20 @@
 //         -self.a.log2()
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovers_exact_line_and_rejects_constant_regressor() {
+        let data: Paired<f64> = (0..6).map(|i| (f64::from(i), 1.5 - 0.5 * f64::from(i))).collect();
+        let line = LeastSquares.compute(&data);
+        assert!((line.intercept - 1.5).abs() < 1e-12);
+        assert!((line.slope + 0.5).abs() < 1e-12);
+        assert!(line.residuals(&data).iter().all(|r| r.abs() < 1e-12));
+
+        let constant: Paired<f64> = [(2.0, 1.0), (2.0, 3.0)].into_iter().collect();
+        assert!(Slope.compute(&constant).is_nan());
+    }
+}
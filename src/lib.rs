@@ -13,8 +13,9 @@ mod least_squares;
 mod display;
 
 pub use math::*;
-pub use crate::sample::Sample;
+pub use crate::sample::{Paired, Sample, Weighted};
 pub use crate::resample::*;
 pub use crate::statistics::*;
 pub use crate::hypothesis::*;
+pub use crate::least_squares::{Correlation, Intercept, LeastSquares, Line, Slope};
 pub use rand;
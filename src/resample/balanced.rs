@@ -0,0 +1,290 @@
+use std::cmp::Ordering;
+use rand::{Rng, SeedableRng};
+use crate::Sample;
+use super::{Re, ReAt, ReLend, ReStrategy, Streamed, replicate_rng};
+
+/// Balanced bootstrap (Davison, Hinkley & Schechtman, 1986).
+///
+/// Every observation appears exactly `B` times across the `B` replicates:
+/// the replicates are consecutive blocks of a random permutation of `B`
+/// copies of the sample. The first-order term of the Monte Carlo error then
+/// cancels, so the bias estimate `θ̄* - θ̂` of a linear statistic is exact.
+///
+/// The number of replicates is part of the design; the iterator stops after
+/// `replicates`. Blocks are drawn sequentially from the remaining multiset, so
+/// only `n` counters are kept, not the `n·B` permutation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BalancedBootstrap<R: Rng> {
+    pub rng: R,
+    pub replicates: usize,
+}
+
+impl<R: Rng> BalancedBootstrap<R> {
+    pub fn new(rng: R, replicates: usize) -> Self {
+        Self { rng, replicates }
+    }
+}
+
+impl<T: Copy, R: Rng + Clone> Re<Sample<T>> for BalancedBootstrap<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Copy, R: Rng + Clone> ReLend<Sample<T>> for BalancedBootstrap<R> {
+    type Elem = T;
+    type Strategy = BalancedStrategy<R>;
+
+    fn strategy(&self, _sample: &Sample<T>) -> Self::Strategy {
+        BalancedStrategy::new(self.rng.clone(), self.replicates)
+    }
+}
+
+/// Draws blocks of `n` without replacement from `B` copies of the sample.
+pub struct BalancedStrategy<R: Rng> {
+    rng: R,
+    remaining: usize,
+    /// Copies of every observation not used yet
+    pool: Option<Fenwick>,
+}
+
+impl<R: Rng> BalancedStrategy<R> {
+    pub fn new(rng: R, replicates: usize) -> Self {
+        Self {
+            rng,
+            remaining: replicates,
+            pool: None,
+        }
+    }
+}
+
+impl<T: Copy, R: Rng> ReStrategy<Sample<T>, T> for BalancedStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        let data = &sample.data;
+        let n = data.len();
+        let remaining = self.remaining;
+        let pool = self.pool.get_or_insert_with(|| Fenwick::filled(n, remaining));
+
+        buffer.clear();
+        buffer.reserve_exact(n);
+        let mut total = n * remaining;
+        for _ in 0..n {
+            let index = pool.find(self.rng.gen_range(0..total));
+            pool.decrement(index);
+            total -= 1;
+            if let Some(&x) = data.get(index) {
+                buffer.push(x);
+            }
+        }
+
+        self.remaining -= 1;
+        true
+    }
+
+    #[inline]
+    fn size_hint(&self, _sample: &Sample<T>) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// Binary indexed tree over the copy counts: draws a uniformly random copy in `O(log n)`.
+struct Fenwick {
+    /// 1-based; node `i` sums the counts of `(i - lowbit(i), i]`
+    tree: Vec<usize>,
+}
+
+impl Fenwick {
+    /// `n` counts equal to `value`.
+    fn filled(n: usize, value: usize) -> Self {
+        let tree = (0..=n).map(|i| value * (i & i.wrapping_neg())).collect();
+        Self { tree }
+    }
+
+    fn decrement(&mut self, index: usize) {
+        let mut i = index + 1;
+        while let Some(node) = self.tree.get_mut(i) {
+            *node -= 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Observation holding the `u`-th remaining copy (0-based).
+    fn find(&self, mut u: usize) -> usize {
+        let n = self.tree.len() - 1;
+        let mut position = 0;
+        let mut step = if n == 0 { 0 } else { 1 << n.ilog2() };
+        while step > 0 {
+            if let Some(&count) = self.tree.get(position + step) {
+                if count <= u {
+                    position += step;
+                    u -= count;
+                }
+            }
+            step >>= 1;
+        }
+        position
+    }
+}
+
+/// Antithetic bootstrap (Hall, 1989).
+///
+/// Replicates come in pairs: the second one takes the indices of the first
+/// and maps each observation to its mirror in rank order (the `k`-th smallest
+/// to the `k`-th largest). The two members of a pair are negatively
+/// correlated for monotone statistics, so averages over pairs have lower
+/// Monte Carlo variance than over independent replicates.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AntitheticBootstrap<R: Rng> {
+    pub rng: R,
+}
+
+impl<R: Rng> AntitheticBootstrap<R> {
+    pub fn new(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+/// Position of the rank-mirrored observation of every observation.
+fn mirror<T: PartialOrd>(data: &[T]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    order.sort_by(|&a, &b| match (data.get(a), data.get(b)) {
+        (Some(x), Some(y)) => x.partial_cmp(y).unwrap_or(Ordering::Equal),
+        _ => Ordering::Equal,
+    });
+
+    let mut mirrored = vec![0; data.len()];
+    for (&position, &partner) in order.iter().zip(order.iter().rev()) {
+        if let Some(slot) = mirrored.get_mut(position) {
+            *slot = partner;
+        }
+    }
+    mirrored
+}
+
+impl<T: Copy + PartialOrd, R: Rng + SeedableRng + Clone> Re<Sample<T>> for AntitheticBootstrap<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Copy + PartialOrd, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for AntitheticBootstrap<R> {
+    type Elem = T;
+    type Strategy = Streamed<AntitheticStrategy<R>>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        Streamed::new(&self.rng, AntitheticStrategy::new(self.rng.clone(), mirror(&sample.data)))
+    }
+}
+
+impl<T: Copy + PartialOrd, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for AntitheticBootstrap<R> {
+    /// A pair is drawn from the stream of its even index; odd indices are the mirrored member.
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let mut strategy = AntitheticStrategy::new(replicate_rng(&self.rng, index - index % 2), mirror(&sample.data));
+        let mut buffer = Vec::new();
+        for _ in 0..=index % 2 {
+            strategy.mutate(sample, &mut buffer);
+        }
+        Sample::new(buffer)
+    }
+}
+
+/// Alternates a fresh bootstrap draw with its rank-mirrored partner.
+pub struct AntitheticStrategy<R: Rng> {
+    rng: R,
+    mirror: Vec<usize>,
+    indices: Vec<usize>,
+    pending: bool,
+}
+
+impl<R: Rng> AntitheticStrategy<R> {
+    /// `mirror[i]` is the partner position of observation `i`.
+    pub fn new(rng: R, mirror: Vec<usize>) -> Self {
+        Self {
+            rng,
+            mirror,
+            indices: Vec::new(),
+            pending: false,
+        }
+    }
+}
+
+// The mirrored member ignores the RNG, so reseeding keeps the pending draw
+impl_reseed!(AntitheticStrategy<R>);
+
+impl<T: Copy, R: Rng> ReStrategy<Sample<T>, T> for AntitheticStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        let n = data.len();
+
+        if self.pending {
+            for index in &mut self.indices {
+                *index = self.mirror.get(*index).copied().unwrap_or(*index);
+            }
+        } else {
+            self.indices.clear();
+            self.indices.extend((0..n).map(|_| self.rng.gen_range(0..n)));
+        }
+        self.pending = !self.pending;
+
+        buffer.clear();
+        buffer.extend(self.indices.iter().filter_map(|&i| data.get(i).copied()));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Mean, Statistic};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    /// Monte Carlo bias estimate `θ̄* - θ̂` of the mean from `b` replicates
+    fn bias<S: Re<Sample<f64>, Item = Sample<f64>>>(resampler: &S, sample: &Sample<f64>, b: usize) -> f64 {
+        let replicates: Vec<f64> = resampler.re(sample).take(b).map(|r| Mean.compute(&r)).collect();
+        Mean.compute(&replicates) - Mean.compute(sample)
+    }
+
+    #[test]
+    fn balance_and_antithetic_pairs_reduce_monte_carlo_error() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(12);
+        let sample: Sample<f64> = (0..40).map(|_| rng.r#gen::<f64>().powi(2)).collect();
+
+        // Every observation appears exactly B times, and the mean's bias vanishes
+        let balanced = BalancedBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(1), 25);
+        let positions = Sample::new((0..40).collect::<Vec<usize>>());
+        let mut counts = [0; 40];
+        for replicate in balanced.re(&positions) {
+            assert_eq!(replicate.len(), 40);
+            replicate.data.iter().for_each(|&i| counts.get_mut(i).into_iter().for_each(|c| *c += 1));
+        }
+        assert!(counts.iter().all(|&c| c == 25), "{counts:?}");
+        assert!(bias(&balanced, &sample, 25).abs() < 1e-12);
+
+        // Partners hold the rank-mirrored observations, sequentially and by index
+        let antithetic = AntitheticBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(2));
+        let rank = |x: f64| sample.data.iter().filter(|&&y| y < x).count();
+        let mirrored = |first: &Sample<f64>, second: &Sample<f64>| {
+            first.data.iter().zip(&second.data).all(|(&a, &b)| rank(a) + rank(b) == 39)
+        };
+        let sequential: Vec<Sample<f64>> = antithetic.re(&sample).take(2).collect();
+        assert!(matches!(sequential.as_slice(), [a, b] if mirrored(a, b)));
+        assert!(mirrored(&antithetic.re_at(&sample, 6), &antithetic.re_at(&sample, 7)));
+
+        // Spread of the bias estimate over independent runs at B = 50
+        let spread = |estimates: Vec<f64>| estimates.iter().map(|e| e * e).sum::<f64>().sqrt();
+        let plain = spread((0..30).map(|s| bias(&Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(s)), &sample, 50)).collect());
+        let paired = spread(
+            (0..30)
+                .map(|s| bias(&AntitheticBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(s)), &sample, 50))
+                .collect(),
+        );
+        assert!(paired < plain / 2.0, "{paired} vs {plain}");
+    }
+}
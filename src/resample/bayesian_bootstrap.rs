@@ -0,0 +1,249 @@
+use std::sync::Arc;
+use num_traits::{Float, FromPrimitive};
+use rand::{Rng, SeedableRng};
+use rand::distributions::Distribution;
+use statrs::distribution::Gamma;
+use crate::{Sample, Weighted};
+use super::{Re, ReAt, replicate_rng, replicate_rngs};
+
+/// How replicate weights are drawn
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Weighting {
+    /// Dirichlet(α, …, α) probabilities; α = 1 is Rubin's (1981) Bayesian bootstrap
+    Dirichlet { concentration: f64 },
+    /// Multinomial(n; 1/n, …, 1/n) counts: the classical bootstrap without copying
+    Multinomial,
+}
+
+impl Default for Weighting {
+    fn default() -> Self {
+        Self::Dirichlet { concentration: 1.0 }
+    }
+}
+
+impl Weighting {
+    /// Overwrite `weights` with the weights of one replicate of `n` observations.
+    pub fn fill<T, R>(&self, rng: &mut R, n: usize, weights: &mut Vec<T>)
+    where
+        T: Float + FromPrimitive,
+        R: Rng + ?Sized,
+    {
+        weights.clear();
+        match *self {
+            Self::Dirichlet { concentration } => {
+                // Normalized Gamma(α, 1) draws; α = 1 reduces to exponential spacings
+                let gamma = Gamma::new(concentration, 1.0).expect("Valid Dirichlet concentration");
+                let exponential = (concentration - 1.0).abs() < f64::EPSILON;
+                let mut total = T::zero();
+                for _ in 0..n {
+                    let g = if exponential {
+                        -(1.0 - rng.r#gen::<f64>()).ln()
+                    } else {
+                        gamma.sample(rng)
+                    };
+                    let g = T::from_f64(g).expect("weight fits in float");
+                    total = total + g;
+                    weights.push(g);
+                }
+                for w in weights.iter_mut() {
+                    *w = *w / total;
+                }
+            }
+            Self::Multinomial => {
+                weights.resize(n, T::zero());
+                for _ in 0..n {
+                    if let Some(count) = weights.get_mut(rng.gen_range(0..n)) {
+                        *count = *count + T::one();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Weighted bootstrap: each replicate keeps the original observations and
+/// draws a fresh weight vector instead of copying a resample.
+///
+/// With Dirichlet weights the replicates follow Rubin's Bayesian bootstrap,
+/// whose smooth posterior-like distribution suits small samples; with
+/// multinomial counts they reproduce `Bootstrap` exactly for weight-aware
+/// statistics (`Mean`, `Variance`, `Skewness`, `Kurtosis`, `CDF`, `Quantile`).
+///
+/// Every `re` call copies the observations once and shares the copy between its
+/// replicates; `re_at` copies them per replicate. For random access or parallel
+/// resampling of one sample, share the copy with [`fitted`](Self::fitted).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BayesianBootstrap<R: Rng> {
+    pub rng: R,
+    pub weighting: Weighting,
+}
+
+impl<R: Rng> BayesianBootstrap<R> {
+    /// Rubin's Bayesian bootstrap: Dirichlet(1, …, 1) weights
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            weighting: Weighting::default(),
+        }
+    }
+
+    /// Multinomial counts (the classical bootstrap expressed through weights)
+    pub fn multinomial(rng: R) -> Self {
+        Self::new(rng).with_weighting(Weighting::Multinomial)
+    }
+
+    pub fn with_weighting(mut self, weighting: Weighting) -> Self {
+        self.weighting = weighting;
+        self
+    }
+
+    /// Dirichlet(α, …, α) weights; α < 1 concentrates mass on fewer observations
+    pub fn with_concentration(self, concentration: f64) -> Self {
+        assert!(concentration > 0.0, "Dirichlet concentration must be positive");
+        self.with_weighting(Weighting::Dirichlet { concentration })
+    }
+
+    /// Resampler weighting one copy of the observations of `sample`, shared by
+    /// every replicate.
+    pub fn fitted<T: Copy>(&self, sample: &Sample<T>) -> FittedBayesian<T, R>
+    where
+        R: Clone,
+    {
+        FittedBayesian {
+            rng: self.rng.clone(),
+            weighting: self.weighting,
+            data: Arc::from(sample.data.as_slice()),
+        }
+    }
+}
+
+impl<T, R> Re<Sample<T>> for BayesianBootstrap<R>
+where
+    T: Float + FromPrimitive,
+    R: Rng + SeedableRng + Clone,
+{
+    type Item = Weighted<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        let fitted = self.fitted(sample);
+        replicate_rngs(&self.rng).map(move |mut rng| fitted.draw(&mut rng))
+    }
+}
+
+impl<T, R> ReAt<Sample<T>> for BayesianBootstrap<R>
+where
+    T: Float + FromPrimitive,
+    R: Rng + SeedableRng + Clone,
+{
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        self.fitted(sample).re_at(sample, index)
+    }
+}
+
+/// [`BayesianBootstrap`] holding one shared copy of the observations.
+///
+/// Applies to the sample it was built from and panics on samples of another
+/// length. Draws match those of the `BayesianBootstrap` it was built by.
+#[derive(Clone, Debug)]
+pub struct FittedBayesian<T, R: Rng> {
+    pub rng: R,
+    pub weighting: Weighting,
+    data: Arc<[T]>,
+}
+
+impl<T, R: Rng> FittedBayesian<T, R> {
+    /// Shared observations with a fresh weight vector drawn by `rng`.
+    fn draw<S: Rng>(&self, rng: &mut S) -> Weighted<T>
+    where
+        T: Float + FromPrimitive,
+    {
+        let mut weights = Vec::with_capacity(self.data.len());
+        self.weighting.fill(rng, self.data.len(), &mut weights);
+        Weighted {
+            data: Arc::clone(&self.data),
+            weights,
+        }
+    }
+}
+
+impl<T, R> Re<Sample<T>> for FittedBayesian<T, R>
+where
+    T: Float + FromPrimitive,
+    R: Rng + SeedableRng + Clone,
+{
+    type Item = Weighted<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        assert_eq!(sample.len(), self.data.len(), "Fitted to a sample of another length");
+        replicate_rngs(&self.rng).map(move |mut rng| self.draw(&mut rng))
+    }
+}
+
+impl<T, R> ReAt<Sample<T>> for FittedBayesian<T, R>
+where
+    T: Float + FromPrimitive,
+    R: Rng + SeedableRng + Clone,
+{
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        assert_eq!(sample.len(), self.data.len(), "Fitted to a sample of another length");
+        self.draw(&mut replicate_rng(&self.rng, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, CDF, Mean, Quantile, SE, Skewness, Statistic, Variance};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    fn sample() -> Sample<f64> {
+        [2.1, 3.4, 1.9, 5.6, 4.4, 3.3, 2.8, 6.1, 3.9, 4.0].into_iter().collect()
+    }
+
+    #[test]
+    fn multinomial_counts_match_copied_resamples() {
+        let sample = sample();
+        let rng = Xoshiro256PlusPlus::seed_from_u64(3);
+
+        for weighted in BayesianBootstrap::multinomial(rng).re(&sample).take(20) {
+            let copied: Sample<f64> = weighted
+                .iter()
+                .flat_map(|(&x, &c)| std::iter::repeat_n(x, c as usize))
+                .collect();
+
+            let pairs: [(f64, f64); 3] = [
+                (Mean.compute(&weighted), Mean.compute(&copied)),
+                (Variance::default().compute(&weighted), Variance::default().compute(&copied)),
+                (Skewness::default().compute(&weighted), Skewness::default().compute(&copied)),
+            ];
+            for (w, c) in pairs {
+                assert!((w - c).abs() < 1e-9 || (w.is_nan() && c.is_nan()), "{w} vs {c}");
+            }
+
+            let median: f64 = Quantile::median().compute(&CDF.compute(&weighted));
+            let expected: f64 = Quantile::median().compute(&CDF.compute(&copied));
+            assert!((median - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn dirichlet_weights_are_probabilities_and_plug_into_se() {
+        let sample = sample();
+        let resampler = BayesianBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(5));
+        for weighted in resampler.re(&sample).take(10) {
+            assert!(weighted.weights.iter().all(|&w| w > 0.0));
+            assert!((weighted.total() - 1.0).abs() < 1e-12);
+        }
+
+        // Posterior SD of the mean is close to the bootstrap SE
+        let bayes: f64 = SE::new(Mean, resampler.clone(), 4000).compute(&sample);
+        let classic: f64 = SE::new(Mean, Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(5)), 4000)
+            .compute(&sample);
+        assert!((bayes / classic - 1.0).abs() < 0.1, "{bayes} vs {classic}");
+
+        // Random access through the fitted resampler shares one copy of the data
+        let fitted = resampler.fitted(&sample);
+        let (first, second) = (fitted.re_at(&sample, 0), fitted.re_at(&sample, 1));
+        assert!(Arc::ptr_eq(&first.data, &second.data));
+    }
+}
@@ -1,6 +1,6 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use crate::Sample;
-use super::Re;
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, replicate_rng};
 
 #[derive(Clone, Copy, Default)]
 pub struct Bootstrap<R: Rng> {
@@ -13,46 +13,59 @@ impl<R: Rng> Bootstrap<R> {
     }
 }
 
-impl<T: Copy, R: Rng + Clone> Re<Sample<T>> for Bootstrap<R> {
+impl<T: Copy, R: Rng + SeedableRng + Clone> Re<Sample<T>> for Bootstrap<R> {
     type Item = Sample<T>;
 
     fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
-        Box::new(BootstrapIter::new(&sample.data, self.rng.clone()))
+        self.re_lend(sample)
     }
 }
 
-pub struct BootstrapIter<'a, T, R: Rng> {
-    data: &'a [T],
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for Bootstrap<R> {
+    type Elem = T;
+    type Strategy = Streamed<BootstrapStrategy<R>>;
+
+    fn strategy(&self, _sample: &Sample<T>) -> Self::Strategy {
+        Streamed::new(&self.rng, BootstrapStrategy::new(self.rng.clone()))
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for Bootstrap<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        ReIter::new(BootstrapStrategy::new(replicate_rng(&self.rng, index)), sample)
+            .next()
+            .expect("bootstrap iterator is unbounded")
+    }
+}
+
+/// Draws `n` observations with replacement.
+pub struct BootstrapStrategy<R: Rng> {
     rng: R,
-    buffer: Vec<T>,
 }
 
-impl<'a, T: Copy, R: Rng> BootstrapIter<'a, T, R> {
-    fn new(data: &'a [T], rng: R) -> Self {
-        Self {
-            buffer: Vec::with_capacity(data.len()),
-            data,
-            rng,
-        }
+impl<R: Rng> BootstrapStrategy<R> {
+    pub fn new(rng: R) -> Self {
+        Self { rng }
     }
 }
 
-impl<'a, T: Copy, R: Rng> Iterator for BootstrapIter<'a, T, R> {
-    type Item = Sample<T>;
+impl_reseed!(BootstrapStrategy<R>);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let n = self.data.len();
-        self.buffer.clear();
-        self.buffer.reserve_exact(n);
+impl<T: Copy, R: Rng> ReStrategy<Sample<T>, T> for BootstrapStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        let n = data.len();
+        buffer.clear();
+        buffer.reserve_exact(n);
 
         unsafe {
-            self.buffer.set_len(n);
+            buffer.set_len(n);
             for i in 0..n {
                 let idx = self.rng.gen_range(0..n);
-                *self.buffer.get_unchecked_mut(i) = *self.data.get_unchecked(idx);
+                *buffer.get_unchecked_mut(i) = *data.get_unchecked(idx);
             }
         }
 
-        Some(Sample::new(std::mem::take(&mut self.buffer)))
+        true
     }
 }
@@ -0,0 +1,281 @@
+use std::hash::Hash;
+use std::sync::Arc;
+use rand::{Rng, SeedableRng};
+use crate::Sample;
+use super::{Gather, Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, group_indices, replicate_rng, replicate_rngs};
+
+/// Cluster bootstrap: draws whole clusters with replacement.
+///
+/// Observations of a cluster (instrument run, patient…) are kept together, so
+/// within-cluster correlation is carried into every replicate. With
+/// [`two_stage`](Self::two_stage) each drawn cluster is additionally resampled
+/// with replacement internally. Replicate length varies with the sizes of the
+/// drawn clusters.
+///
+/// The clusters are fixed at construction, so the resampler only applies to the
+/// sample it was built for and panics on data of any other length. Replicates
+/// do not fit the keys either; to nest a cluster bootstrap, e.g. as the inner
+/// SE resampler of `StudentizedBootstrap`, use [`KeyedClusterBootstrap`].
+#[derive(Clone, Debug)]
+pub struct ClusterBootstrap<R: Rng> {
+    pub rng: R,
+    pub two_stage: bool,
+    clusters: Arc<[Vec<usize>]>,
+}
+
+impl<R: Rng> ClusterBootstrap<R> {
+    /// `clusters[i]` is the cluster of observation `i`.
+    pub fn new<K: Eq + Hash>(rng: R, clusters: &[K]) -> Self {
+        Self {
+            rng,
+            two_stage: false,
+            clusters: group_indices(clusters).into(),
+        }
+    }
+
+    /// Also resample observations within every drawn cluster
+    pub fn two_stage(mut self) -> Self {
+        self.two_stage = true;
+        self
+    }
+
+    /// Number of observations the resampler was built for
+    pub fn len(&self) -> usize {
+        self.clusters.iter().map(Vec::len).sum()
+    }
+
+    /// Check if the resampler was built for no observations
+    pub fn is_empty(&self) -> bool {
+        self.clusters.is_empty()
+    }
+
+    /// Number of clusters
+    pub fn n_clusters(&self) -> usize {
+        self.clusters.len()
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> Re<Sample<T>> for ClusterBootstrap<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for ClusterBootstrap<R> {
+    type Elem = T;
+    type Strategy = Streamed<ClusterStrategy<R>>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        assert_eq!(sample.len(), self.len(), "One cluster key per observation");
+        Streamed::new(&self.rng, ClusterStrategy::new(self.rng.clone(), Arc::clone(&self.clusters), self.two_stage))
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for ClusterBootstrap<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        assert_eq!(sample.len(), self.len(), "One cluster key per observation");
+        let strategy = ClusterStrategy::new(
+            replicate_rng(&self.rng, index),
+            Arc::clone(&self.clusters),
+            self.two_stage,
+        );
+        ReIter::new(strategy, sample)
+            .next()
+            .expect("cluster iterator is unbounded")
+    }
+}
+
+/// Draws `G` of the `G` clusters with replacement, reporting every drawn
+/// observation together with the position of its cluster in the draw.
+fn draw_clusters<R: Rng>(rng: &mut R, clusters: &[Vec<usize>], two_stage: bool, mut emit: impl FnMut(usize, usize)) {
+    let n_clusters = clusters.len();
+    for position in 0..n_clusters {
+        let members = clusters
+            .get(rng.gen_range(0..n_clusters))
+            .expect("cluster drawn within range");
+
+        if two_stage {
+            // Second stage: |cluster| draws with replacement inside the cluster
+            let size = members.len();
+            for _ in 0..size {
+                if let Some(&i) = members.get(rng.gen_range(0..size)) {
+                    emit(position, i);
+                }
+            }
+        } else {
+            members.iter().for_each(|&i| emit(position, i));
+        }
+    }
+}
+
+/// Concatenates `G` clusters drawn with replacement out of `G`.
+pub struct ClusterStrategy<R: Rng> {
+    rng: R,
+    clusters: Arc<[Vec<usize>]>,
+    two_stage: bool,
+}
+
+impl<R: Rng> ClusterStrategy<R> {
+    pub fn new(rng: R, clusters: Arc<[Vec<usize>]>, two_stage: bool) -> Self {
+        Self {
+            rng,
+            clusters,
+            two_stage,
+        }
+    }
+}
+
+impl_reseed!(ClusterStrategy<R>);
+
+impl<T: Copy, R: Rng> ReStrategy<Sample<T>, T> for ClusterStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        buffer.clear();
+        draw_clusters(&mut self.rng, &self.clusters, self.two_stage, |_, i| {
+            buffer.extend(data.get(i).copied());
+        });
+        true
+    }
+}
+
+/// Cluster bootstrap of data that carries its own cluster keys.
+///
+/// Resamples `(keys, data)` pairs for any [`Gather`] data: the clusters are
+/// read off `keys` on every call, and the observations of the `j`-th drawn
+/// cluster get key `j` in the replicate. A replicate is therefore again keyed
+/// data, with a cluster drawn twice counted as two clusters, and can be
+/// resampled once more, as the inner SE of `StudentizedBootstrap` does.
+/// Draws match those of [`ClusterBootstrap`] built from the same keys.
+#[derive(Clone, Debug)]
+pub struct KeyedClusterBootstrap<R: Rng> {
+    pub rng: R,
+    pub two_stage: bool,
+}
+
+impl<R: Rng> KeyedClusterBootstrap<R> {
+    pub fn new(rng: R) -> Self {
+        Self { rng, two_stage: false }
+    }
+
+    /// Also resample observations within every drawn cluster
+    pub fn two_stage(mut self) -> Self {
+        self.two_stage = true;
+        self
+    }
+}
+
+/// Replicate of keyed data from one draw of `clusters`.
+fn keyed_replicate<D: Gather, R: Rng>(
+    rng: &mut R,
+    clusters: &[Vec<usize>],
+    two_stage: bool,
+    values: &D,
+) -> (Vec<usize>, D) {
+    let (mut keys, mut indices) = (Vec::new(), Vec::new());
+    draw_clusters(rng, clusters, two_stage, |position, i| {
+        keys.push(position);
+        indices.push(i);
+    });
+    (keys, values.gather(&indices))
+}
+
+impl<D: Gather, R: Rng + SeedableRng + Clone> Re<(Vec<usize>, D)> for KeyedClusterBootstrap<R> {
+    type Item = (Vec<usize>, D);
+
+    fn re(&self, data: &(Vec<usize>, D)) -> impl Iterator<Item = Self::Item> {
+        let (keys, values) = data;
+        let clusters = group_indices(keys);
+        replicate_rngs(&self.rng).map(move |mut rng| keyed_replicate(&mut rng, &clusters, self.two_stage, values))
+    }
+}
+
+impl<D: Gather, R: Rng + SeedableRng + Clone> ReAt<(Vec<usize>, D)> for KeyedClusterBootstrap<R> {
+    fn re_at(&self, data: &(Vec<usize>, D), index: usize) -> Self::Item {
+        let (keys, values) = data;
+        keyed_replicate(&mut replicate_rng(&self.rng, index), &group_indices(keys), self.two_stage, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Mean, SE, Statistic, StudentizedBootstrap};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn clusters_are_drawn_whole() {
+        // Every cluster holds copies of a single value
+        let keys = [0, 0, 0, 1, 1, 2, 2, 2, 2];
+        let sample: Sample<f64> = [5.0, 5.0, 5.0, 7.0, 7.0, 9.0, 9.0, 9.0, 9.0].into_iter().collect();
+        let resampler = ClusterBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(4), &keys);
+        assert_eq!(resampler.n_clusters(), 3);
+
+        for resample in resampler.re(&sample).take(50) {
+            let count = |v: f64| resample.data.iter().filter(|&&x| (x - v).abs() < 1e-12).count();
+            assert_eq!(count(5.0) % 3, 0);
+            assert_eq!(count(7.0) % 2, 0);
+            assert_eq!(count(9.0) % 4, 0);
+        }
+    }
+
+    #[test]
+    fn cluster_se_accounts_for_within_cluster_correlation() {
+        // 10 clusters of 10 nearly identical observations
+        let keys: Vec<usize> = (0..100).map(|i| i / 10).collect();
+        let sample: Sample<f64> = (0..100)
+            .map(|i| f64::from(i / 10) + 0.01 * f64::from(i % 10))
+            .collect();
+        let rng = Xoshiro256PlusPlus::seed_from_u64(8);
+
+        let cluster = ClusterBootstrap::new(rng.clone(), &keys);
+        let clustered: f64 = SE::new(Mean, cluster.clone(), 2000).compute(&sample);
+        let two_stage: f64 = SE::new(Mean, cluster.two_stage(), 2000).compute(&sample);
+        let iid: f64 = SE::new(Mean, Bootstrap::new(rng), 2000).compute(&sample);
+
+        // Effective sample size is ~10, not 100
+        assert!(clustered > 2.5 * iid, "{clustered} vs {iid}");
+        assert!(two_stage >= 0.9 * clustered, "{two_stage} vs {clustered}");
+    }
+
+    #[test]
+    #[should_panic(expected = "One cluster key per observation")]
+    fn fixed_clusters_reject_other_samples() {
+        let keys: Vec<usize> = (0..60).map(|i| i / 6).collect();
+        let shorter: Sample<f64> = (0..50).map(f64::from).collect();
+        let fixed = ClusterBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(9), &keys);
+        let _: f64 = SE::new(Mean, fixed, 100).compute(&shorter);
+    }
+
+    /// Mean of the values of keyed data
+    #[derive(Clone, Copy)]
+    struct KeyedMean;
+
+    impl Statistic<(Vec<usize>, Sample<f64>), f64> for KeyedMean {
+        fn compute(&self, (_, values): &(Vec<usize>, Sample<f64>)) -> f64 {
+            Mean.compute(values)
+        }
+    }
+
+    #[test]
+    fn keyed_clusters_nest_inside_studentized_intervals() {
+        let keys: Vec<usize> = (0..60).map(|i| i / 6).collect();
+        let values: Sample<f64> = (0..60).map(|i| f64::from(i / 6) + 0.1 * f64::from(i % 6)).collect();
+        let rng = Xoshiro256PlusPlus::seed_from_u64(9);
+
+        // Keyed draws match the fixed ones and relabel the drawn clusters
+        let fixed = ClusterBootstrap::new(rng.clone(), &keys);
+        let keyed = KeyedClusterBootstrap::new(rng.clone());
+        let data = (keys, values.clone());
+        for ((replicate_keys, replicate), expected) in keyed.re(&data).zip(fixed.re(&values)).take(20) {
+            assert_eq!(replicate.data, expected.data);
+            assert_eq!(replicate_keys, (0..60).map(|i| i / 6).collect::<Vec<_>>());
+        }
+        assert_eq!(keyed.re_at(&data, 3).1.data, fixed.re_at(&values, 3).data);
+
+        let se = SE::new(KeyedMean, KeyedClusterBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(10)), 100);
+        let interval = StudentizedBootstrap::new(KeyedMean, se, keyed, 200, 0.9).compute(&data);
+        assert!(interval.lower < 5.25 && 5.25 < interval.upper, "{interval:?}");
+    }
+}
@@ -0,0 +1,427 @@
+use std::hash::Hash;
+use std::sync::Arc;
+use rand::{Rng, SeedableRng};
+use rand::seq::SliceRandom;
+use super::{ExactShuffle, Gather, Re, ReAt, group_indices, replicate_rng};
+
+/// Train/test partition of observation indices, both sorted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Split {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+impl Split {
+    /// `(train, test)` rows of `data`.
+    pub fn apply<D: Gather>(&self, data: &D) -> (D, D) {
+        (data.gather(&self.train), data.gather(&self.test))
+    }
+}
+
+/// Split testing on fold `fold` of `assignment` (fold id of every observation).
+fn fold_split(assignment: &[usize], fold: usize) -> Split {
+    let (test, train) = (0..assignment.len()).partition(|&i| assignment.get(i) == Some(&fold));
+    Split { train, test }
+}
+
+/// One split per non-empty fold of `assignment`.
+fn folds(assignment: &[usize], k: usize) -> Vec<Split> {
+    (0..k)
+        .map(|fold| fold_split(assignment, fold))
+        .filter(|split| !split.test.is_empty())
+        .collect()
+}
+
+/// Fold ids of a random partition of `n` observations into `min(k, n)`
+/// near-equal, non-empty folds.
+fn shuffled_folds<R: Rng>(rng: &mut R, n: usize, k: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..n).collect();
+    order.shuffle(rng);
+    let mut assignment = vec![0; n];
+    for (position, &i) in order.iter().enumerate() {
+        if let Some(fold) = assignment.get_mut(i) {
+            *fold = position * k.min(n) / n;
+        }
+    }
+    assignment
+}
+
+/// K-fold cross-validation: a random partition into `k` folds, each one the
+/// test set once.
+///
+/// As a resampler it yields `(train, test)` pairs of any [`Gather`] data, so
+/// aligned columns are split together.
+#[derive(Clone, Copy, Debug)]
+pub struct KFold<R: Rng> {
+    pub rng: R,
+    pub k: usize,
+}
+
+impl<R: Rng> KFold<R> {
+    pub fn new(rng: R, k: usize) -> Self {
+        assert!(k >= 2, "K-fold needs at least two folds");
+        Self { rng, k }
+    }
+}
+
+impl<R: Rng + Clone> KFold<R> {
+    /// Train/test indices of the folds of `n` observations.
+    pub fn splits(&self, n: usize) -> impl Iterator<Item = Split> + use<R> {
+        let assignment = shuffled_folds(&mut self.rng.clone(), n, self.k);
+        folds(&assignment, self.k).into_iter()
+    }
+}
+
+impl<D: Gather, R: Rng + Clone> Re<D> for KFold<R> {
+    type Item = (D, D);
+
+    fn re(&self, data: &D) -> impl Iterator<Item = Self::Item> {
+        self.splits(data.rows()).map(move |split| split.apply(data))
+    }
+}
+
+impl<D: Gather, R: Rng + Clone> ReAt<D> for KFold<R> {
+    /// Split testing on fold `index`; only that fold is materialised.
+    fn re_at(&self, data: &D, index: usize) -> Self::Item {
+        assert!(index < self.count(data), "fold index out of range");
+        let assignment = shuffled_folds(&mut self.rng.clone(), data.rows(), self.k);
+        fold_split(&assignment, index).apply(data)
+    }
+
+    #[inline]
+    fn count(&self, data: &D) -> usize {
+        self.k.min(data.rows())
+    }
+}
+
+/// Stratified K-fold: every fold keeps the class proportions of the sample.
+///
+/// Members of each stratum are shuffled and dealt to the folds in turn, so
+/// fold sizes differ by at most one and rare classes reach every fold.
+#[derive(Clone, Debug)]
+pub struct StratifiedKFold<R: Rng> {
+    pub rng: R,
+    pub k: usize,
+    strata: Arc<[Vec<usize>]>,
+}
+
+impl<R: Rng> StratifiedKFold<R> {
+    /// `labels[i]` is the class of observation `i`.
+    pub fn new<K: Eq + Hash>(rng: R, k: usize, labels: &[K]) -> Self {
+        assert!(k >= 2, "K-fold needs at least two folds");
+        Self {
+            rng,
+            k,
+            strata: group_indices(labels).into(),
+        }
+    }
+
+    /// Number of observations the splitter was built for
+    pub fn len(&self) -> usize {
+        self.strata.iter().map(Vec::len).sum()
+    }
+
+    /// Check if the splitter was built for no observations
+    pub fn is_empty(&self) -> bool {
+        self.strata.is_empty()
+    }
+}
+
+impl<R: Rng + Clone> StratifiedKFold<R> {
+    /// Train/test indices of the stratified folds.
+    pub fn splits(&self) -> impl Iterator<Item = Split> + use<R> {
+        let mut rng = self.rng.clone();
+        let mut assignment = vec![0; self.len()];
+        let mut dealt = 0;
+        for members in self.strata.iter() {
+            let mut members = members.clone();
+            members.shuffle(&mut rng);
+            for i in members {
+                if let Some(fold) = assignment.get_mut(i) {
+                    *fold = dealt % self.k;
+                }
+                dealt += 1;
+            }
+        }
+        folds(&assignment, self.k).into_iter()
+    }
+}
+
+impl<D: Gather, R: Rng + Clone> Re<D> for StratifiedKFold<R> {
+    type Item = (D, D);
+
+    fn re(&self, data: &D) -> impl Iterator<Item = Self::Item> {
+        assert_eq!(data.rows(), self.len(), "One label per observation");
+        self.splits().map(move |split| split.apply(data))
+    }
+}
+
+/// K-fold cross-validation repeated over independent partitions.
+///
+/// Repetition `r` draws its partition from the `r`-th replicate stream of the
+/// RNG, so sequential and random access (`re_at`) give the same splits.
+#[derive(Clone, Copy, Debug)]
+pub struct RepeatedKFold<R: Rng> {
+    pub rng: R,
+    pub k: usize,
+    pub repeats: usize,
+}
+
+impl<R: Rng> RepeatedKFold<R> {
+    pub fn new(rng: R, k: usize, repeats: usize) -> Self {
+        assert!(k >= 2, "K-fold needs at least two folds");
+        Self { rng, k, repeats }
+    }
+}
+
+impl<R: Rng + SeedableRng + Clone> RepeatedKFold<R> {
+    /// Fold ids of repetition `repeat`.
+    fn assignment(&self, n: usize, repeat: usize) -> Vec<usize> {
+        shuffled_folds(&mut replicate_rng(&self.rng, repeat), n, self.k)
+    }
+
+    /// Train/test indices of all `k·repeats` folds.
+    pub fn splits(&self, n: usize) -> impl Iterator<Item = Split> + use<R> {
+        let this = self.clone();
+        (0..self.repeats).flat_map(move |repeat| folds(&this.assignment(n, repeat), this.k))
+    }
+}
+
+impl<D: Gather, R: Rng + SeedableRng + Clone> Re<D> for RepeatedKFold<R> {
+    type Item = (D, D);
+
+    fn re(&self, data: &D) -> impl Iterator<Item = Self::Item> {
+        self.splits(data.rows()).map(move |split| split.apply(data))
+    }
+}
+
+impl<D: Gather, R: Rng + SeedableRng + Clone> ReAt<D> for RepeatedKFold<R> {
+    fn re_at(&self, data: &D, index: usize) -> Self::Item {
+        assert!(index < self.count(data), "fold index out of range");
+        let folds = self.k.min(data.rows());
+        fold_split(&self.assignment(data.rows(), index / folds), index % folds).apply(data)
+    }
+
+    #[inline]
+    fn count(&self, data: &D) -> usize {
+        self.k.min(data.rows()) * self.repeats
+    }
+}
+
+/// Leave-p-out: every subset of `p` observations is the test set once.
+///
+/// There are `C(n, p)` splits, enumerated lazily in lexicographic order.
+#[derive(Clone, Copy, Debug)]
+pub struct LeavePOut {
+    pub p: usize,
+}
+
+impl LeavePOut {
+    pub fn new(p: usize) -> Self {
+        assert!(p >= 1, "Leave-p-out needs p ≥ 1");
+        Self { p }
+    }
+
+    /// Train/test indices of all `C(n, p)` splits.
+    pub fn splits(&self, n: usize) -> impl Iterator<Item = Split> + use<> {
+        let p = self.p;
+        let mut test: Option<Vec<usize>> = (p <= n).then(|| (0..p).collect());
+        std::iter::from_fn(move || {
+            let current = test.clone()?;
+
+            // Advance to the next combination: bump the rightmost index that can move
+            test = test.take().and_then(|mut next| {
+                let position = (0..p).rev().find(|&j| next.get(j).is_some_and(|&c| c < n - p + j))?;
+                let start = next.get(position).copied()? + 1;
+                for (offset, slot) in next.iter_mut().skip(position).enumerate() {
+                    *slot = start + offset;
+                }
+                Some(next)
+            });
+
+            let train = (0..n).filter(|i| current.binary_search(i).is_err()).collect();
+            Some(Split { train, test: current })
+        })
+    }
+}
+
+impl<D: Gather> Re<D> for LeavePOut {
+    type Item = (D, D);
+
+    fn re(&self, data: &D) -> impl Iterator<Item = Self::Item> {
+        self.splits(data.rows()).map(move |split| split.apply(data))
+    }
+}
+
+impl<D: Gather> ReAt<D> for LeavePOut {
+    /// Split `index`, unranked in the combinatorial number system.
+    fn re_at(&self, data: &D, index: usize) -> Self::Item {
+        assert!(index < self.count(data), "split index out of range");
+        // Test indices first, then the training complement, both ascending
+        let mut order = ExactShuffle::splits(self.p).unrank(data.rows(), index);
+        let train = order.split_off(self.p);
+        Split { train, test: order }.apply(data)
+    }
+
+    /// `C(n, p)`, saturating at `usize::MAX`
+    #[inline]
+    fn count(&self, data: &D) -> usize {
+        ExactShuffle::splits(self.p).configurations(data.rows())
+    }
+}
+
+/// Rolling-origin evaluation for time series (Tashman, 2000).
+///
+/// The forecast origin moves forward by `step`; each split trains on the
+/// observations before the origin (all of them, or the last `window`) and
+/// tests on the next `horizon`. The test set never precedes the training set,
+/// so no future information leaks into the fit.
+#[derive(Clone, Copy, Debug)]
+pub struct RollingOrigin {
+    /// Size of the first training set
+    pub initial: usize,
+    /// Number of test observations after each origin
+    pub horizon: usize,
+    /// Shift of the origin between splits
+    pub step: usize,
+    /// Fixed training window (expanding window if `None`)
+    pub window: Option<usize>,
+}
+
+impl RollingOrigin {
+    /// Expanding window starting with `initial` observations, origin moved by `horizon`.
+    pub fn new(initial: usize, horizon: usize) -> Self {
+        assert!(initial > 0 && horizon > 0, "Training and test sets must be non-empty");
+        Self {
+            initial,
+            horizon,
+            step: horizon,
+            window: None,
+        }
+    }
+
+    pub fn with_step(mut self, step: usize) -> Self {
+        assert!(step > 0, "Origin step must be positive");
+        self.step = step;
+        self
+    }
+
+    /// Train on the last `window` observations only (rolling window).
+    pub fn with_window(mut self, window: usize) -> Self {
+        assert!(window > 0, "Training window must be positive");
+        self.window = Some(window);
+        self
+    }
+
+    /// Number of splits of a series of `n` observations
+    pub fn len(&self, n: usize) -> usize {
+        if n < self.initial + self.horizon {
+            0
+        } else {
+            (n - self.initial - self.horizon) / self.step + 1
+        }
+    }
+
+    /// Train/test indices of every origin.
+    pub fn splits(&self, n: usize) -> impl Iterator<Item = Split> + use<> {
+        let this = *self;
+        (0..self.len(n)).map(move |index| this.split(index))
+    }
+
+    /// Split at origin `initial + index·step`.
+    fn split(&self, index: usize) -> Split {
+        let origin = self.initial + index * self.step;
+        let start = self.window.map_or(0, |window| origin.saturating_sub(window));
+        Split {
+            train: (start..origin).collect(),
+            test: (origin..origin + self.horizon).collect(),
+        }
+    }
+}
+
+impl<D: Gather> Re<D> for RollingOrigin {
+    type Item = (D, D);
+
+    fn re(&self, data: &D) -> impl Iterator<Item = Self::Item> {
+        self.splits(data.rows()).map(move |split| split.apply(data))
+    }
+}
+
+impl<D: Gather> ReAt<D> for RollingOrigin {
+    fn re_at(&self, data: &D, index: usize) -> Self::Item {
+        assert!(index < self.count(data), "origin index out of range");
+        self.split(index).apply(data)
+    }
+
+    #[inline]
+    fn count(&self, data: &D) -> usize {
+        self.len(data.rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sample;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    fn random_access_matches<S>(splitter: &S, sample: &Sample<f64>) -> bool
+    where
+        S: ReAt<Sample<f64>, Item = (Sample<f64>, Sample<f64>)>,
+    {
+        splitter.re(sample).enumerate().all(|(index, (train, test))| {
+            let (train_at, test_at) = splitter.re_at(sample, index);
+            train.data == train_at.data && test.data == test_at.data
+        })
+    }
+
+    #[test]
+    fn splitters_partition_the_observations() {
+        let rng = Xoshiro256PlusPlus::seed_from_u64(3);
+
+        // Every observation is tested exactly once per K-fold pass
+        let mut tested = vec![0; 23];
+        for split in KFold::new(rng.clone(), 5).splits(23) {
+            assert_eq!(split.train.len() + split.test.len(), 23);
+            assert!((4..=5).contains(&split.test.len()));
+            split.test.iter().for_each(|&i| tested.get_mut(i).into_iter().for_each(|t| *t += 1));
+        }
+        assert!(tested.iter().all(|&t| t == 1));
+
+        // Stratified folds carry 2 of the 10 minority observations each
+        let labels: Vec<bool> = (0..50).map(|i| i % 5 == 0).collect();
+        for split in StratifiedKFold::new(rng.clone(), 5, &labels).splits() {
+            assert_eq!(split.test.iter().filter(|&&i| labels.get(i) == Some(&true)).count(), 2);
+        }
+
+        // Repeats are independent partitions; random access matches iteration
+        let sample: Sample<f64> = (0..20).map(f64::from).collect();
+        let repeated = RepeatedKFold::new(rng, 4, 3);
+        let sequential: Vec<(Sample<f64>, Sample<f64>)> = repeated.re(&sample).collect();
+        assert_eq!(sequential.len(), repeated.count(&sample));
+        for (index, (train, test)) in sequential.iter().enumerate() {
+            let (train_at, test_at) = repeated.re_at(&sample, index);
+            assert_eq!((&train.data, &test.data), (&train_at.data, &test_at.data));
+        }
+
+        // C(5, 2) = 10 distinct test pairs
+        let pairs: Vec<Split> = LeavePOut::new(2).splits(5).collect();
+        assert_eq!(pairs.len(), 10);
+        assert_eq!(pairs.last().map(|s| s.test.clone()), Some(vec![3, 4]));
+
+        // Direct unranking agrees with iteration for every splitter
+        assert!(random_access_matches(&KFold::new(Xoshiro256PlusPlus::seed_from_u64(8), 6), &sample));
+        assert!(random_access_matches(&LeavePOut::new(3), &sample));
+        assert!(random_access_matches(&RollingOrigin::new(5, 2).with_window(4), &sample));
+        assert_eq!(KFold::new(Xoshiro256PlusPlus::seed_from_u64(8), 30).re(&sample).count(), 20);
+
+        // Rolling origin never tests before it trains
+        let rolling = RollingOrigin::new(10, 3).with_step(2).with_window(6);
+        let splits: Vec<Split> = rolling.splits(20).collect();
+        assert_eq!(splits.len(), rolling.len(20));
+        for split in &splits {
+            assert_eq!(split.train.len(), 6);
+            assert!(split.train.last() < split.test.first());
+        }
+    }
+}
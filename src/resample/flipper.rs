@@ -1,6 +1,6 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use crate::Sample;
-use super::Re;
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Reseed, Streamed, replicate_rng};
 
 /// Customizable flipping strategy trait
 pub trait Flip<T> {
@@ -70,33 +70,58 @@ impl<R: Rng, F> Flipper<R, F> {
 
 impl<T, R, F> Re<Sample<T>> for Flipper<R, F>
 where
-    R: Rng + Clone,
+    R: Rng + SeedableRng + Clone,
     F: Flip<T> + Clone,
     T: Copy,
 {
     type Item = Sample<T>;
 
     fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
-        FlipperIter::new(&sample.data, self.rng.clone(), self.flip.clone())
+        self.re_lend(sample)
     }
 }
 
-pub struct FlipperIter<'a, T, R: Rng, F: Flip<T>> {
-    data: &'a [T],
+impl<T, R, F> ReLend<Sample<T>> for Flipper<R, F>
+where
+    R: Rng + SeedableRng + Clone,
+    F: Flip<T> + Clone,
+    T: Copy,
+{
+    type Elem = T;
+    type Strategy = Streamed<FlipperStrategy<R, F>>;
+
+    fn strategy(&self, _sample: &Sample<T>) -> Self::Strategy {
+        Streamed::new(&self.rng, FlipperStrategy::new(self.rng.clone(), self.flip.clone()))
+    }
+}
+
+impl<T, R, F> ReAt<Sample<T>> for Flipper<R, F>
+where
+    R: Rng + SeedableRng + Clone,
+    F: Flip<T> + Clone,
+    T: Copy,
+{
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let strategy = FlipperStrategy::new(replicate_rng(&self.rng, index), self.flip.clone());
+        ReIter::new(strategy, sample)
+            .next()
+            .expect("flipper iterator is unbounded")
+    }
+}
+
+/// Flips every observation independently with probability 1/2.
+pub struct FlipperStrategy<R: Rng, F> {
     rng: R,
     flip: F,
-    buffer: Vec<T>,
     /// Bit reservoir: holds 64 pre-generated random bits
     bit_reservoir: u64,
     /// Number of consumed bits (0..64)
     bits_consumed: u32,
 }
 
-impl<'a, T: Copy, R: Rng, F: Flip<T>> FlipperIter<'a, T, R, F> {
-    fn new(data: &'a [T], rng: R, flip: F) -> Self {
+impl<R: Rng, F> FlipperStrategy<R, F> {
+    pub fn new(rng: R, flip: F) -> Self {
         Self {
-            buffer: Vec::with_capacity(data.len()),
-            data,
             rng,
             flip,
             bit_reservoir: 0,
@@ -105,25 +130,36 @@ impl<'a, T: Copy, R: Rng, F: Flip<T>> FlipperIter<'a, T, R, F> {
     }
 }
 
-impl<'a, T: Copy, R: Rng, F: Flip<T>> Iterator for FlipperIter<'a, T, R, F> {
-    type Item = Sample<T>;
+impl<R: Rng + SeedableRng, F> Reseed for FlipperStrategy<R, F> {
+    type Rng = R;
+
+    #[inline]
+    fn reseed(&mut self, rng: R) {
+        self.rng = rng;
+        // Bits left over from the old RNG must not leak into the new stream
+        self.bits_consumed = 64;
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let n = self.data.len();
+impl<T: Copy, R: Rng, F: Flip<T>> ReStrategy<Sample<T>, T> for FlipperStrategy<R, F> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        let n = data.len();
+        buffer.clear();
         if n == 0 {
-            return Some(Sample::new(Vec::new()));
+            return true;
         }
 
         // Reuse buffer allocation
-        if self.buffer.capacity() < n {
-            self.buffer.reserve_exact(n);
+        if buffer.capacity() < n {
+            buffer.reserve_exact(n);
         }
 
         unsafe {
-            self.buffer.set_len(n);
-            std::ptr::copy_nonoverlapping(self.data.as_ptr(), self.buffer.as_mut_ptr(), n);
+            buffer.set_len(n);
+            std::ptr::copy_nonoverlapping(data.as_ptr(), buffer.as_mut_ptr(), n);
 
-            let ptr = self.buffer.as_mut_ptr();
+            let ptr = buffer.as_mut_ptr();
             let mut i = 0;
 
             // 8-way unrolled loop consuming 8 bits per iteration
@@ -166,6 +202,146 @@ impl<'a, T: Copy, R: Rng, F: Flip<T>> Iterator for FlipperIter<'a, T, R, F> {
             }
         }
 
-        Some(Sample::new(std::mem::take(&mut self.buffer)))
+        true
+    }
+}
+
+/// Exhaustive sign flipping: yields each of the `2ⁿ` flip patterns exactly once.
+///
+/// Pattern `k` flips observation `i` iff bit `i` of `k` is set, so replicate 0
+/// is the data itself. Meant for small samples (n ≤ ~20), where enumeration is
+/// cheaper than drawing random flips and gives exact permutation p-values.
+/// Patterns address only the first `usize::BITS` observations; later ones are
+/// never flipped.
+#[derive(Clone, Copy, Default)]
+pub struct ExactFlipper<F> {
+    pub flip: F,
+}
+
+impl ExactFlipper<SignBitFlip> {
+    /// Exhaustive sign flipping for IEEE 754 floats
+    pub fn sign() -> Self {
+        Self { flip: SignBitFlip }
+    }
+}
+
+impl<F> ExactFlipper<F> {
+    pub fn with_strategy(flip: F) -> Self {
+        Self { flip }
+    }
+
+    /// Number of flip patterns of `n` observations (`usize::MAX` on overflow)
+    pub fn patterns(n: usize) -> usize {
+        u32::try_from(n)
+            .ok()
+            .and_then(|n| 1usize.checked_shl(n))
+            .unwrap_or(usize::MAX)
+    }
+}
+
+impl<T: Copy, F: Flip<T> + Clone> Re<Sample<T>> for ExactFlipper<F> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Copy, F: Flip<T> + Clone> ReLend<Sample<T>> for ExactFlipper<F> {
+    type Elem = T;
+    type Strategy = ExactFlipperStrategy<F>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        ExactFlipperStrategy::new(self.flip.clone(), sample.len())
+    }
+}
+
+impl<T: Copy, F: Flip<T> + Clone> ReAt<Sample<T>> for ExactFlipper<F> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        sample
+            .data
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| self.flip.flip(x, flips(index, i)))
+            .collect()
+    }
+
+    #[inline]
+    fn count(&self, sample: &Sample<T>) -> usize {
+        Self::patterns(sample.len())
+    }
+}
+
+/// Whether pattern `pattern` flips observation `i`; patterns only reach the
+/// first `usize::BITS` observations.
+#[inline]
+fn flips(pattern: usize, i: usize) -> bool {
+    u32::try_from(i)
+        .ok()
+        .and_then(|i| pattern.checked_shr(i))
+        .is_some_and(|bits| bits & 1 == 1)
+}
+
+/// Walks the flip patterns `0, 1, …, 2ⁿ - 1`.
+pub struct ExactFlipperStrategy<F> {
+    flip: F,
+    pattern: usize,
+    total: usize,
+}
+
+impl<F> ExactFlipperStrategy<F> {
+    pub fn new(flip: F, n: usize) -> Self {
+        Self {
+            flip,
+            pattern: 0,
+            total: ExactFlipper::<F>::patterns(n),
+        }
+    }
+}
+
+impl<T: Copy, F: Flip<T>> ReStrategy<Sample<T>, T> for ExactFlipperStrategy<F> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        if self.pattern >= self.total {
+            return false;
+        }
+
+        let pattern = self.pattern;
+        self.pattern += 1;
+
+        buffer.clear();
+        buffer.extend(
+            sample
+                .data
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| self.flip.flip(x, flips(pattern, i))),
+        );
+        true
+    }
+
+    #[inline]
+    fn size_hint(&self, _sample: &Sample<T>) -> (usize, Option<usize>) {
+        let remaining = self.total - self.pattern;
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_patterns_leave_observations_past_the_word_unflipped() {
+        let sample: Sample<f64> = (1..=70).map(f64::from).collect();
+        let flipper = ExactFlipper::sign();
+        assert_eq!(flipper.count(&sample), usize::MAX);
+
+        let last = flipper.re_at(&sample, usize::MAX);
+        let (flipped, kept): (Vec<f64>, Vec<f64>) = last.data.iter().partition(|x| x.is_sign_negative());
+        assert_eq!((flipped.len(), kept.len()), (64, 6));
+
+        let mut draws = flipper.re(&sample);
+        assert_eq!(draws.next().map(|first| first.data), Some(sample.data.clone()));
+        assert_eq!(draws.next().map(|second| second.data.iter().filter(|x| x.is_sign_negative()).count()), Some(1));
     }
 }
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+use crate::{Paired, Sample};
+use super::{Re, ReAt, ReLend, ReStrategy};
+
+/// Row-aligned data that can be subset by observation indices.
+///
+/// Implemented for single columns and for tuples of columns, so one index
+/// draw keeps several aligned columns together.
+pub trait Gather {
+    /// Number of observations (rows)
+    fn rows(&self) -> usize;
+
+    /// Rows `indices` in that order (repeats allowed).
+    fn gather(&self, indices: &[usize]) -> Self;
+}
+
+impl<T: Copy> Gather for Vec<T> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn gather(&self, indices: &[usize]) -> Self {
+        indices.iter().filter_map(|&i| self.get(i).copied()).collect()
+    }
+}
+
+impl<T: Copy> Gather for Sample<T> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn gather(&self, indices: &[usize]) -> Self {
+        Sample::new(self.data.gather(indices))
+    }
+}
+
+impl<T: Copy> Gather for Paired<T> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+
+    fn gather(&self, indices: &[usize]) -> Self {
+        let x: Arc<[T]> = indices.iter().filter_map(|&i| self.x.get(i).copied()).collect();
+        Paired { x, y: self.y.gather(indices) }
+    }
+}
+
+impl<A: Gather, B: Gather> Gather for (A, B) {
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn gather(&self, indices: &[usize]) -> Self {
+        (self.0.gather(indices), self.1.gather(indices))
+    }
+}
+
+impl<A: Gather, B: Gather, C: Gather> Gather for (A, B, C) {
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn gather(&self, indices: &[usize]) -> Self {
+        (self.0.gather(indices), self.1.gather(indices), self.2.gather(indices))
+    }
+}
+
+/// Index output mode of a sample resampler (`Bootstrap`, `Shuffle`,
+/// `Jackknife`, `Subsample`, …).
+///
+/// The wrapped resampler is run on the positions `0, …, n-1`, so every draw
+/// is an index vector. [`indices`](Self::indices) and [`counts`](Self::counts)
+/// expose the draws directly; as a resampler, `Indexed` applies each draw to
+/// all columns of [`Gather`] data at once. Draws match those the wrapped
+/// resampler makes on a `Sample` of the same size.
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_xoshiro::Xoshiro256PlusPlus;
+/// use zima::{Bootstrap, Correlation, Indexed, Paired, SE, Statistic};
+///
+/// let data: Paired<f64> = [(1.0, 2.1), (2.0, 3.9), (3.0, 6.2), (4.0, 7.8)].into_iter().collect();
+/// let resampler = Indexed::new(Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(1)));
+/// let se: f64 = SE::new(Correlation, resampler, 200).compute(&data);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Indexed<R> {
+    pub resampler: R,
+}
+
+/// Positions `0, …, n-1` the wrapped resampler draws from.
+fn positions(n: usize) -> Sample<usize> {
+    Sample::new((0..n).collect())
+}
+
+impl<R> Indexed<R> {
+    pub fn new(resampler: R) -> Self {
+        Self { resampler }
+    }
+}
+
+impl<R: ReLend<Sample<usize>, Elem = usize>> Indexed<R> {
+    /// Index vectors of the replicates of `n` observations.
+    pub fn indices(&self, n: usize) -> impl Iterator<Item = Vec<usize>> + use<R> {
+        let positions = positions(n);
+        let mut strategy = self.resampler.strategy(&positions);
+        std::iter::from_fn(move || {
+            let mut indices = Vec::new();
+            strategy.mutate(&positions, &mut indices).then_some(indices)
+        })
+    }
+
+    /// Multiplicity of every observation in each replicate of `n` observations.
+    pub fn counts(&self, n: usize) -> impl Iterator<Item = Vec<usize>> + use<R> {
+        self.indices(n).map(move |indices| {
+            let mut counts = vec![0; n];
+            for i in indices {
+                if let Some(count) = counts.get_mut(i) {
+                    *count += 1;
+                }
+            }
+            counts
+        })
+    }
+}
+
+impl<D: Gather, R: ReLend<Sample<usize>, Elem = usize>> Re<D> for Indexed<R> {
+    type Item = D;
+
+    fn re(&self, data: &D) -> impl Iterator<Item = Self::Item> {
+        self.indices(data.rows()).map(move |indices| data.gather(&indices))
+    }
+
+    #[inline]
+    fn variance_scale(&self, data: &D, replicates: usize) -> f64 {
+        self.resampler.variance_scale(&positions(data.rows()), replicates)
+    }
+}
+
+impl<D, R> ReAt<D> for Indexed<R>
+where
+    D: Gather,
+    R: ReLend<Sample<usize>, Elem = usize> + ReAt<Sample<usize>, Item = Sample<usize>>,
+{
+    fn re_at(&self, data: &D, index: usize) -> Self::Item {
+        data.gather(&self.resampler.re_at(&positions(data.rows()), index).data)
+    }
+
+    #[inline]
+    fn count(&self, data: &D) -> usize {
+        self.resampler.count(&positions(data.rows()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Correlation, Jackknife, PairsBootstrap, SE, SamplingMode, Shuffle, Statistic, Subsample};
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn one_draw_keeps_columns_aligned() {
+        let rng = Xoshiro256PlusPlus::seed_from_u64(5);
+        let x: Sample<f64> = (0..30).map(f64::from).collect();
+        let y: Vec<f64> = x.data.iter().map(|v| 2.0 * v).collect();
+
+        // Same draws as resampling the values directly
+        let bootstrap = Indexed::new(Bootstrap::new(rng.clone()));
+        let direct: Vec<Sample<f64>> = Bootstrap::new(rng.clone()).re(&x).take(10).collect();
+        for ((xs, ys), expected) in bootstrap.re(&(x.clone(), y.clone())).zip(direct) {
+            assert_eq!(xs.data, expected.data);
+            assert!(xs.data.iter().zip(&ys).all(|(a, b)| (2.0 * a - b).abs() < 1e-12));
+        }
+
+        assert!(bootstrap.counts(30).take(10).all(|counts| counts.iter().sum::<usize>() == 30));
+        assert!(Indexed::new(Shuffle::new(rng.clone())).counts(30).take(10).all(|c| c.iter().all(|&k| k == 1)));
+        let jackknife: Vec<Vec<usize>> = Indexed::new(Jackknife).counts(30).collect();
+        assert_eq!(jackknife.len(), 30);
+        assert!(jackknife.iter().all(|c| c.iter().filter(|&&k| k == 0).count() == 1));
+        let subsample = Subsample::new(rng.clone()).with_mode(SamplingMode::WithoutReplacement);
+        assert!(Indexed::new(subsample).counts(30).take(10).all(|c| c.iter().all(|&k| k <= 1)));
+
+        // Row bootstrap of pairs reproduces the pairs bootstrap draw for draw
+        let mut noise = Xoshiro256PlusPlus::seed_from_u64(6);
+        let data: Paired<f64> = (0..200)
+            .map(|i| {
+                let x = f64::from(i) / 200.0;
+                (x, x + noise.gen_range(-0.5..0.5))
+            })
+            .collect();
+        let indexed: f64 = SE::new(Correlation, bootstrap, 500).compute(&data);
+        let pairs: f64 = SE::new(Correlation, PairsBootstrap::new(rng), 500).compute(&data);
+        assert!(indexed > 0.0 && (indexed - pairs).abs() < 1e-12, "{indexed} vs {pairs}");
+    }
+}
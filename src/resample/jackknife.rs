@@ -1,5 +1,6 @@
+use std::hash::Hash;
+use std::sync::Arc;
 use crate::Sample;
-use std::iter::FusedIterator;
 use super::*;
 
 #[derive(Clone, Copy, Default)]
@@ -14,93 +15,469 @@ impl Jackknife {
 impl<T: Copy> Re<Sample<T>> for Jackknife {
     type Item = Sample<T>;
     fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
-        JackknifeIter::new(&sample.data)
+        self.re_lend(sample)
+    }
+
+    /// Leave-one-out inflation: `(n-1)/B`, i.e. `(n-1)/n` over all `n` replicates
+    #[inline]
+    fn variance_scale(&self, sample: &Sample<T>, replicates: usize) -> f64 {
+        delete_d_scale(sample.len(), 1, replicates)
     }
 }
 
-pub struct JackknifeIter<'a, T: Copy> {
-    data: &'a [T],
-    buffer: Vec<T>,
-    omit_idx: usize,
-    n: usize,
+impl<T: Copy> ReLend<Sample<T>> for Jackknife {
+    type Elem = T;
+    type Strategy = JackknifeStrategy;
+
+    fn strategy(&self, _sample: &Sample<T>) -> Self::Strategy {
+        JackknifeStrategy::default()
+    }
 }
 
-impl<'a, T: Copy> JackknifeIter<'a, T> {
-    #[inline(always)]
-    fn new(data: &'a [T]) -> Self {
-        let n = data.len();
-        let mut buffer = Vec::with_capacity(n.saturating_sub(1));
-        // Safety: buffer is empty, set_len will be called before reads
-        unsafe { buffer.set_len(0) };
+impl<T: Copy> ReAt<Sample<T>> for Jackknife {
+    /// Sample with observation `index` left out.
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let data = &sample.data;
+        let mut buffer = Vec::with_capacity(data.len().saturating_sub(1));
+        buffer.extend(data.iter().take(index));
+        buffer.extend(data.iter().skip(index + 1));
+        Sample::new(buffer)
+    }
 
-        Self {
-            data,
-            buffer,
-            omit_idx: 0,
-            n,
-        }
+    #[inline]
+    fn count(&self, sample: &Sample<T>) -> usize {
+        sample.data.len()
     }
 }
 
-impl<'a, T: Copy> Iterator for JackknifeIter<'a, T> {
-    type Item = Sample<T>;
+/// Leave-one-out: omits observations `0, 1, …, n-1` in turn.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JackknifeStrategy {
+    omit_idx: usize,
+}
 
+impl<T: Copy> ReStrategy<Sample<T>, T> for JackknifeStrategy {
     #[inline(always)]
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.omit_idx >= self.n {
-            return None;
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        let n = data.len();
+        if self.omit_idx >= n {
+            return false;
         }
 
         let omit = self.omit_idx;
         self.omit_idx += 1;
-        let new_len = self.n - 1;
+        let new_len = n - 1;
 
         // Reuse buffer capacity without reallocation
-        if self.buffer.capacity() < new_len {
-            self.buffer.reserve_exact(new_len);
+        buffer.clear();
+        if buffer.capacity() < new_len {
+            buffer.reserve_exact(new_len);
         }
 
         unsafe {
             // Critical optimization: use single contiguous write operation
             // Compiler will autovectorize these copies for primitive types
-            self.buffer.set_len(new_len);
+            buffer.set_len(new_len);
 
             // Copy prefix [0..omit)
             if omit > 0 {
                 std::ptr::copy_nonoverlapping(
-                    self.data.as_ptr(),
-                    self.buffer.as_mut_ptr(),
+                    data.as_ptr(),
+                    buffer.as_mut_ptr(),
                     omit,
                 );
             }
 
             // Copy suffix [omit+1..n) -> [omit..end)
             // Single branch-free operation when omit is in middle
-            if omit < self.n - 1 {
+            if omit < n - 1 {
                 std::ptr::copy_nonoverlapping(
-                    self.data.as_ptr().add(omit + 1),
-                    self.buffer.as_mut_ptr().add(omit),
-                    self.n - omit - 1,
+                    data.as_ptr().add(omit + 1),
+                    buffer.as_mut_ptr().add(omit),
+                    n - omit - 1,
                 );
             }
         }
 
-        // Zero-cost transfer: buffer capacity preserved for next iteration
-        // mem::replace avoids double-free while maintaining capacity
-        let sample_data = std::mem::replace(
-            &mut self.buffer,
-            Vec::with_capacity(new_len),
+        true
+    }
+
+    #[inline]
+    fn size_hint(&self, sample: &Sample<T>) -> (usize, Option<usize>) {
+        let remaining = sample.data.len().saturating_sub(self.omit_idx);
+        (remaining, Some(remaining))
+    }
+}
+
+/// Delete-d jackknife scaling: `(n-d) / (d·B)`, exact for the mean.
+///
+/// NaN when nothing is left after deleting `d` observations.
+#[inline]
+fn delete_d_scale(n: usize, d: usize, replicates: usize) -> f64 {
+    if n <= d {
+        return f64::NAN;
+    }
+    (n - d) as f64 / (d as f64 * replicates as f64)
+}
+
+/// Exhaustive delete-d jackknife: every subset of `n - d` observations, once.
+///
+/// Consistent for non-smooth statistics such as the median when
+/// `√n < d < n` (Shao & Wu, 1989), at the cost of `C(n, d)` replicates; see
+/// [`RandomDeleteJackknife`] for large `n`. Kept observations stay in their
+/// original order.
+#[derive(Clone, Copy, Debug)]
+pub struct DeleteJackknife {
+    pub d: usize,
+}
+
+impl DeleteJackknife {
+    pub fn new(d: usize) -> Self {
+        assert!(d > 0, "Delete at least one observation");
+        Self { d }
+    }
+
+    /// `d = ⌈√n⌉`, the smallest choice that is consistent for quantiles
+    pub fn sqrt_n(n: usize) -> Self {
+        Self::new(((n as f64).sqrt().ceil() as usize).max(1))
+    }
+
+    fn kept(&self, n: usize) -> ExactShuffle {
+        assert!(self.d < n, "Cannot delete {} of {} observations", self.d, n);
+        ExactShuffle::splits(n - self.d)
+    }
+}
+
+impl<T: Copy> Re<Sample<T>> for DeleteJackknife {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+
+    #[inline]
+    fn variance_scale(&self, sample: &Sample<T>, replicates: usize) -> f64 {
+        delete_d_scale(sample.len(), self.d, replicates)
+    }
+}
+
+impl<T: Copy> ReLend<Sample<T>> for DeleteJackknife {
+    type Elem = T;
+    type Strategy = DeleteJackknifeStrategy;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        let n = sample.len();
+        DeleteJackknifeStrategy {
+            subsets: ExactShuffleStrategy::new(self.kept(n).enumeration, n),
+            keep: n - self.d,
+        }
+    }
+}
+
+impl<T: Copy> ReAt<Sample<T>> for DeleteJackknife {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let mut resample = self.kept(sample.len()).re_at(sample, index);
+        resample.data.truncate(sample.len() - self.d);
+        resample
+    }
+
+    #[inline]
+    fn count(&self, sample: &Sample<T>) -> usize {
+        self.kept(sample.len()).configurations(sample.len())
+    }
+}
+
+/// Enumerates the kept subsets in lexicographic order.
+pub struct DeleteJackknifeStrategy {
+    subsets: ExactShuffleStrategy,
+    keep: usize,
+}
+
+impl<T: Copy> ReStrategy<Sample<T>, T> for DeleteJackknifeStrategy {
+    #[inline]
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        // Splits put the kept subset first, the deleted observations last
+        let more = self.subsets.mutate(sample, buffer);
+        buffer.truncate(self.keep);
+        more
+    }
+
+    #[inline]
+    fn size_hint(&self, sample: &Sample<T>) -> (usize, Option<usize>) {
+        ReStrategy::<Sample<T>, T>::size_hint(&self.subsets, sample)
+    }
+}
+
+/// Random-subset delete-d jackknife: `B` subsets of `n - d` observations drawn
+/// without replacement, for when `C(n, d)` is out of reach.
+#[derive(Clone, Copy, Debug)]
+pub struct RandomDeleteJackknife<R: Rng> {
+    pub rng: R,
+    pub d: usize,
+}
+
+impl<R: Rng> RandomDeleteJackknife<R> {
+    pub fn new(rng: R, d: usize) -> Self {
+        assert!(d > 0, "Delete at least one observation");
+        Self { rng, d }
+    }
+
+    fn keep(&self, n: usize) -> usize {
+        assert!(self.d < n, "Cannot delete {} of {} observations", self.d, n);
+        n - self.d
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> Re<Sample<T>> for RandomDeleteJackknife<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+
+    #[inline]
+    fn variance_scale(&self, sample: &Sample<T>, replicates: usize) -> f64 {
+        delete_d_scale(sample.len(), self.d, replicates)
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for RandomDeleteJackknife<R> {
+    type Elem = T;
+    type Strategy = Streamed<SubsampleStrategy<R>>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        let strategy = SubsampleStrategy::new(
+            self.rng.clone(),
+            self.keep(sample.len()),
+            SamplingMode::WithoutReplacement,
+        );
+        Streamed::new(&self.rng, strategy)
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for RandomDeleteJackknife<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let strategy = SubsampleStrategy::new(
+            replicate_rng(&self.rng, index),
+            self.keep(sample.len()),
+            SamplingMode::WithoutReplacement,
         );
+        ReIter::new(strategy, sample)
+            .next()
+            .expect("subsample iterator is unbounded")
+    }
+}
+
+/// How observations are grouped for [`GroupedJackknife`]
+#[derive(Clone, Debug)]
+pub enum Grouping {
+    /// Contiguous blocks of `size` observations (the last one may be shorter)
+    Blocks { size: usize },
+    /// Observation indices of every labelled group
+    Labels(Arc<[Vec<usize>]>),
+}
+
+/// Grouped (block) jackknife: leaves out one whole group at a time.
+///
+/// Contiguous blocks suit dependent series; labels suit batches such as runs
+/// or patients. The variance uses the delete-a-group factor `(g-1)/g`, which
+/// assumes groups of roughly equal size.
+#[derive(Clone, Debug)]
+pub struct GroupedJackknife {
+    pub grouping: Grouping,
+}
+
+impl GroupedJackknife {
+    /// Drop contiguous blocks of `size` observations
+    pub fn blocks(size: usize) -> Self {
+        assert!(size > 0, "Block size must be positive");
+        Self {
+            grouping: Grouping::Blocks { size },
+        }
+    }
 
-        Some(Sample::new(sample_data))
+    /// Drop the observations sharing a label; `labels[i]` belongs to observation `i`
+    pub fn labelled<K: Eq + Hash>(labels: &[K]) -> Self {
+        Self {
+            grouping: Grouping::Labels(group_indices(labels).into()),
+        }
     }
 
+    /// Number of groups `g` among `n` observations
+    fn group_count(&self, n: usize) -> usize {
+        match &self.grouping {
+            Grouping::Blocks { size } => n.div_ceil(*size),
+            Grouping::Labels(groups) => groups.len(),
+        }
+    }
+
+    fn groups(&self, n: usize) -> Arc<[Vec<usize>]> {
+        match &self.grouping {
+            Grouping::Blocks { size } => (0..n)
+                .step_by(*size)
+                .map(|start| (start..(start + size).min(n)).collect())
+                .collect(),
+            Grouping::Labels(groups) => {
+                assert_eq!(
+                    groups.iter().map(Vec::len).sum::<usize>(),
+                    n,
+                    "One label per observation"
+                );
+                Arc::clone(groups)
+            }
+        }
+    }
+}
+
+impl<T: Copy> Re<Sample<T>> for GroupedJackknife {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+
+    /// Delete-a-group inflation: `(g-1)/B`, i.e. `(g-1)/g` over all `g` groups
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = self.n.saturating_sub(self.omit_idx);
+    fn variance_scale(&self, sample: &Sample<T>, replicates: usize) -> f64 {
+        (self.group_count(sample.len()) as f64 - 1.0) / replicates as f64
+    }
+}
+
+impl<T: Copy> ReLend<Sample<T>> for GroupedJackknife {
+    type Elem = T;
+    type Strategy = GroupedJackknifeStrategy;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        GroupedJackknifeStrategy {
+            groups: self.groups(sample.len()),
+            omit_group: 0,
+        }
+    }
+}
+
+impl<T: Copy> ReAt<Sample<T>> for GroupedJackknife {
+    /// Sample with group `index` left out.
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let groups = self.groups(sample.len());
+        let omitted = groups.get(index).map_or(&[][..], Vec::as_slice);
+        Sample::new(without(&sample.data, omitted))
+    }
+
+    #[inline]
+    fn count(&self, sample: &Sample<T>) -> usize {
+        self.groups(sample.len()).len()
+    }
+}
+
+/// `data` without the observations at the ascending indices `omitted`.
+fn without<T: Copy>(data: &[T], omitted: &[usize]) -> Vec<T> {
+    let mut buffer = Vec::with_capacity(data.len().saturating_sub(omitted.len()));
+    extend_without(&mut buffer, data, omitted);
+    buffer
+}
+
+fn extend_without<T: Copy>(buffer: &mut Vec<T>, data: &[T], omitted: &[usize]) {
+    let mut skip = omitted.iter().peekable();
+    for (i, &x) in data.iter().enumerate() {
+        if skip.peek() == Some(&&i) {
+            skip.next();
+        } else {
+            buffer.push(x);
+        }
+    }
+}
+
+/// Leaves out groups `0, 1, …, g-1` in turn.
+pub struct GroupedJackknifeStrategy {
+    groups: Arc<[Vec<usize>]>,
+    omit_group: usize,
+}
+
+impl<T: Copy> ReStrategy<Sample<T>, T> for GroupedJackknifeStrategy {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let Some(omitted) = self.groups.get(self.omit_group) else {
+            return false;
+        };
+        self.omit_group += 1;
+
+        buffer.clear();
+        extend_without(buffer, &sample.data, omitted);
+        true
+    }
+
+    #[inline]
+    fn size_hint(&self, _sample: &Sample<T>) -> (usize, Option<usize>) {
+        let remaining = self.groups.len().saturating_sub(self.omit_group);
         (remaining, Some(remaining))
     }
 }
 
-impl<'a, T: Copy> ExactSizeIterator for JackknifeIter<'a, T> {}
-impl<'a, T: Copy> FusedIterator for JackknifeIter<'a, T> {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mean, SE, Statistic, Variance};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    fn sample() -> Sample<f64> {
+        [4.2, 1.3, 5.5, 2.8, 3.1, 6.0, 2.2, 4.9, 3.7, 1.8].into_iter().collect()
+    }
+
+    fn standard_error(sample: &Sample<f64>) -> f64 {
+        let variance: f64 = Variance::default().compute(sample);
+        (variance / sample.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn jackknife_variants_reproduce_standard_error_of_mean() {
+        let sample = sample();
+        let expected = standard_error(&sample);
+
+        let leave_one_out: f64 = SE::jackknife(Mean).compute(&sample);
+        let delete_3: f64 = SE::new(Mean, DeleteJackknife::new(3), usize::MAX).compute(&sample);
+        let singletons: f64 = SE::new(Mean, GroupedJackknife::blocks(1), usize::MAX).compute(&sample);
+
+        for se in [leave_one_out, delete_3, singletons] {
+            assert!((se - expected).abs() < 1e-12, "{se} vs {expected}");
+        }
+
+        // Inflation uses the sample size, not the number of replicates taken
+        assert!((Jackknife.variance_scale(&sample, 5) - 9.0 / 5.0).abs() < 1e-12);
+        assert!((GroupedJackknife::blocks(2).variance_scale(&sample, 2) - 2.0).abs() < 1e-12);
+
+        // Nothing to delete leaves the SE undefined
+        let empty: f64 = SE::jackknife(Mean).compute(&Sample::new(vec![]));
+        assert!(empty.is_nan());
+        assert!(Jackknife.variance_scale(&Sample::new(vec![1.0]), 1).is_nan());
+
+        // Random subsets approach the exhaustive value
+        let random: f64 = SE::new(
+            Mean,
+            RandomDeleteJackknife::new(Xoshiro256PlusPlus::seed_from_u64(6), 3),
+            20_000,
+        )
+        .compute(&sample);
+        assert!((random / expected - 1.0).abs() < 0.05, "{random} vs {expected}");
+    }
+
+    #[test]
+    fn delete_d_and_groups_enumerate_expected_replicates() {
+        let sample = sample();
+        let delete_2 = DeleteJackknife::new(2);
+        assert_eq!(delete_2.count(&sample), 45);
+        let replicates: Vec<Sample<f64>> = delete_2.re(&sample).collect();
+        assert_eq!(replicates.len(), 45);
+        assert!(replicates.iter().all(|r| r.len() == 8));
+        for (index, replicate) in replicates.iter().enumerate() {
+            assert_eq!(delete_2.re_at(&sample, index).data, replicate.data);
+        }
+
+        let labels = ['x', 'y', 'x', 'z', 'y', 'x', 'z', 'y', 'x', 'z'];
+        let grouped = GroupedJackknife::labelled(&labels);
+        let sizes: Vec<usize> = grouped.re(&sample).map(|r| r.len()).collect();
+        assert_eq!(sizes, vec![6, 7, 7]);
+
+        let blocks: Vec<usize> = GroupedJackknife::blocks(4).re(&sample).map(|r| r.len()).collect();
+        assert_eq!(blocks, vec![6, 6, 8]);
+    }
+}
@@ -1,65 +1,424 @@
-pub use rand::Rng;
+pub use rand::{Rng, SeedableRng};
+use crate::Sample;
 
 pub trait Re<T> {
     type Item;
 
     /// Sequential resampling
+    ///
+    /// A randomized resampler's RNG acts as its seed and is never advanced:
+    /// every call to `re` restarts from it and yields the same replicates.
+    /// Fresh replicates come from [`Stream::stream`] or [`Stream::with_seed`].
     fn re(&self, t: &T) -> impl Iterator<Item = Self::Item>;
+
+    /// Factor turning the replicate spread `Σ(θ*ᵢ - θ̄*)²` over `replicates`
+    /// replicates into a variance estimate for the statistic.
+    ///
+    /// `1/(B-1)` suits Monte Carlo resamplers; jackknives override it with
+    /// their inflation factors.
+    #[inline]
+    fn variance_scale(&self, _t: &T, replicates: usize) -> f64 {
+        1.0 / (replicates as f64 - 1.0)
+    }
+}
+
+/// Random-access resampling: the `index`-th replicate is a pure function of
+/// the resampler, the data and `index`.
+///
+/// Randomized resamplers draw every replicate from its own RNG stream seeded by
+/// [`stream_seed`], so replicates can be produced in any order, on any thread,
+/// with bit-identical results. `re` walks the same streams in order: its `i`-th
+/// replicate is `re_at(t, i)`.
+pub trait ReAt<T>: Re<T> {
+    /// The `index`-th replicate.
+    fn re_at(&self, t: &T, index: usize) -> Self::Item;
+
+    /// Number of distinct replicates (`usize::MAX` for unbounded resamplers).
+    #[inline]
+    fn count(&self, _t: &T) -> usize {
+        usize::MAX
+    }
+}
+
+/// Seed of the `index`-th replicate stream derived from `master`.
+///
+/// SplitMix64 finalizer over a Weyl sequence: neighbouring indices give
+/// statistically independent seeds.
+#[inline]
+pub fn stream_seed(master: u64, index: u64) -> u64 {
+    let mut z = master.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Master seed of the replicate streams of `rng`: the first output of a clone,
+/// so the resampler's own RNG is never advanced.
+#[inline]
+fn master_seed<R: Rng + Clone>(rng: &R) -> u64 {
+    rng.clone().next_u64()
+}
+
+/// RNG of the `index`-th replicate stream of `rng`.
+#[inline]
+pub(crate) fn replicate_rng<R: Rng + SeedableRng + Clone>(rng: &R, index: usize) -> R {
+    R::seed_from_u64(stream_seed(master_seed(rng), index as u64))
+}
+
+/// Independent, reproducible RNG streams of a randomized resampler.
+///
+/// A randomized resampler stands for one `(seed, stream)` pair: `re` walks
+/// the replicates of that stream, so a result is a pure function of the seed
+/// and the stream, and repeating `re` repeats the replicates by design.
+/// Several *independent* replicate sets (repeated experiments, one set per
+/// worker) come from distinct streams or seeds. Nested estimators do this
+/// themselves: `StudentizedBootstrap` and `DoubleBootstrap` resample their
+/// `i`-th outer replicate with stream `i` of the inner resampler.
+///
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_xoshiro::Xoshiro256PlusPlus;
+/// use zima::{Bootstrap, Mean, SE, Sample, Statistic, Stream};
+///
+/// let data = Sample::new(vec![1.0, 4.0, 2.5, 3.0, 0.5]);
+/// let bootstrap = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(7));
+/// let ses: Vec<f64> = (0..10).map(|k| SE::new(Mean, bootstrap.stream(k), 200).compute(&data)).collect();
+///
+/// // An audit reruns the same seed
+/// let audit: f64 = SE::new(Mean, bootstrap.with_seed(7), 200).compute(&data);
+/// ```
+pub trait Stream: Sized {
+    /// The resampler with its RNG moved to stream `index`.
+    ///
+    /// The stream seed is derived by [`stream_seed`] from the resampler's RNG
+    /// (which is not advanced) and `index`, in a family disjoint from the
+    /// per-replicate streams of [`ReAt`].
+    fn stream(&self, index: usize) -> Self;
+
+    /// The resampler with its RNG seeded from `seed`; exhaustive resamplers
+    /// are returned unchanged.
+    fn with_seed(self, seed: u64) -> Self;
+}
+
+/// RNG of the `index`-th independent stream of `rng` (see [`Stream`]).
+#[inline]
+pub(crate) fn split_rng<R: Rng + SeedableRng + Clone>(rng: &R, index: usize) -> R {
+    // Re-mixing the master keeps these seeds apart from `replicate_rng`'s
+    let master = stream_seed(master_seed(rng), u64::MAX);
+    R::seed_from_u64(stream_seed(master, index as u64))
+}
+
+macro_rules! impl_stream {
+    ($($resampler:ident<$($param:ident),+>),+ $(,)?) => {$(
+        impl<$($param: Clone),+> Stream for $resampler<$($param),+>
+        where
+            Self: Clone,
+            R: Rng + SeedableRng,
+        {
+            fn stream(&self, index: usize) -> Self {
+                let mut resampler = self.clone();
+                resampler.rng = split_rng(&self.rng, index);
+                resampler
+            }
+
+            fn with_seed(mut self, seed: u64) -> Self {
+                self.rng = R::seed_from_u64(seed);
+                self
+            }
+        }
+    )+};
+}
+
+impl_stream!(
+    AntitheticBootstrap<R>,
+    BalancedBootstrap<R>,
+    BayesianBootstrap<R>,
+    BlockBootstrap<R>,
+    Bootstrap<R>,
+    ClusterBootstrap<R>,
+    FittedBayesian<T, R>,
+    FittedResiduals<T, R>,
+    FittedSieve<R>,
+    Flipper<R, F>,
+    KeyedClusterBootstrap<R>,
+    KFold<R>,
+    PairsBootstrap<R>,
+    ParametricBootstrap<M, R>,
+    RandomDeleteJackknife<R>,
+    RepeatedKFold<R>,
+    ResidualBootstrap<R>,
+    RestrictedShuffle<R>,
+    Shuffle<R>,
+    SieveBootstrap<R>,
+    SmoothedBootstrap<R>,
+    StratifiedBootstrap<R>,
+    StratifiedKFold<R>,
+    WildBootstrap<R>,
+);
+
+impl<R: Rng + SeedableRng + Clone, F: Fn(usize) -> usize + Clone> Stream for Subsample<R, F> {
+    fn stream(&self, index: usize) -> Self {
+        let mut resampler = self.clone();
+        resampler.rng = split_rng(&self.rng, index);
+        resampler
+    }
+
+    fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = R::seed_from_u64(seed);
+        self
+    }
+}
+
+// Exhaustive resamplers draw no random numbers: every stream is the resampler itself
+macro_rules! impl_exhaustive_stream {
+    ($($resampler:ty),+ $(,)?) => {$(
+        impl Stream for $resampler {
+            fn stream(&self, _index: usize) -> Self {
+                self.clone()
+            }
+
+            fn with_seed(self, _seed: u64) -> Self {
+                self
+            }
+        }
+    )+};
+}
+
+impl_exhaustive_stream!(DeleteJackknife, ExactShuffle, GroupedJackknife, Jackknife, LeavePOut, RollingOrigin);
+
+impl<F: Clone> Stream for ExactFlipper<F> {
+    fn stream(&self, _index: usize) -> Self {
+        self.clone()
+    }
+
+    fn with_seed(self, _seed: u64) -> Self {
+        self
+    }
+}
+
+impl<R: Stream> Stream for Indexed<R> {
+    fn stream(&self, index: usize) -> Self {
+        Indexed::new(self.resampler.stream(index))
+    }
+
+    fn with_seed(self, seed: u64) -> Self {
+        Indexed::new(self.resampler.with_seed(seed))
+    }
+}
+
+/// Observation indices of every group, in order of first appearance of the key.
+pub(crate) fn group_indices<K: Eq + std::hash::Hash>(keys: &[K]) -> Vec<Vec<usize>> {
+    let mut position = std::collections::HashMap::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (index, key) in keys.iter().enumerate() {
+        let group = *position.entry(key).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        if let Some(members) = groups.get_mut(group) {
+            members.push(index);
+        }
+    }
+    groups
 }
 
 #[cfg(feature = "rayon")]
 pub trait RePar<T>: Re<T> {
-    type Item;
-
     /// Parallel resampling using `rayon`
-    fn re_par(&self, t: &T) -> impl rayon::prelude::ParallelIterator<Item = Self::Item>;
+    ///
+    /// Indexed, so `.take(b)` and `.collect()` are deterministic: the output
+    /// matches `(0..b).map(|i| self.re_at(t, i))` whatever the thread count.
+    fn re_par(&self, t: &T) -> impl rayon::prelude::IndexedParallelIterator<Item = Self::Item>;
 }
 
-
-pub trait ReStrategy<T>
+#[cfg(feature = "rayon")]
+impl<T, S> RePar<T> for S
+where
+    T: Sync,
+    S: ReAt<T> + Sync,
+    S::Item: Send,
 {
-    #[inline(always)]
-    fn mutate(
-        &mut self,
-        data: &T,
-        output: &mut T,
-    );
+    fn re_par(&self, t: &T) -> impl rayon::prelude::IndexedParallelIterator<Item = Self::Item> {
+        use rayon::prelude::*;
+
+        (0..self.count(t))
+            .into_par_iter()
+            .map(move |index| self.re_at(t, index))
+    }
+}
+
+
+/// Per-replicate resampling kernel writing into a caller-owned buffer.
+///
+/// `D` is the resampled input (e.g. `Sample<T>`), `T` the element type of a replicate.
+pub trait ReStrategy<D, T> {
+    /// Overwrite `buffer` with the next replicate of `data`.
+    /// Returns `false` once the resampler is exhausted.
+    fn mutate(&mut self, data: &D, buffer: &mut Vec<T>) -> bool;
+
+    /// Bounds on the number of remaining replicates.
+    #[inline]
+    fn size_hint(&self, _data: &D) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
 }
 
-/// Rng in strategy
-struct ReIter<S, T> {
+/// Replicate iterator driven by a [`ReStrategy`].
+///
+/// Works both as a lending iterator (`next_ref`, one buffer reused for every
+/// replicate) and as a regular `Iterator` yielding owned samples.
+pub struct ReIter<'a, S, D, T> {
     strategy: S,
-    data: T,
-    buffer: T,
+    data: &'a D,
+    buffer: Sample<T>,
+}
+
+impl<'a, S, D, T> ReIter<'a, S, D, T>
+where
+    S: ReStrategy<D, T>,
+{
+    pub fn new(strategy: S, data: &'a D) -> Self {
+        Self {
+            strategy,
+            data,
+            buffer: Sample::new(Vec::new()),
+        }
+    }
+
+    /// Lending step: the next replicate, valid until the following call.
+    #[inline]
+    pub fn next_ref(&mut self) -> Option<&Sample<T>> {
+        if self.strategy.mutate(self.data, &mut self.buffer.data) {
+            Some(&self.buffer)
+        } else {
+            None
+        }
+    }
 }
 
-impl<S, T> Iterator for ReIter<S, T>
+impl<S, D, T> Iterator for ReIter<'_, S, D, T>
 where
-    T: Copy,
-    S: ReStrategy<T>
+    S: ReStrategy<D, T>,
 {
-    type Item = T;
+    type Item = Sample<T>;
+
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.strategy.mutate(&self.data, &mut self.buffer);
-        Some(self.buffer)
+        if self.strategy.mutate(self.data, &mut self.buffer.data) {
+            // Transfer ownership; the strategy re-reserves on the next call
+            Some(Sample::new(std::mem::take(&mut self.buffer.data)))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.strategy.size_hint(self.data)
     }
 }
 
-struct Bootstrap<R> {
-    rng: R,
+/// Randomized kernel that can restart from another RNG.
+pub trait Reseed {
+    type Rng: SeedableRng;
+
+    /// Continue with `rng` as if the kernel had just been built with it.
+    fn reseed(&mut self, rng: Self::Rng);
+}
+
+macro_rules! impl_reseed {
+    ($($strategy:ident<$($param:ident),+>),+ $(,)?) => {$(
+        impl<$($param),+> $crate::Reseed for $strategy<$($param),+>
+        where
+            R: Rng + SeedableRng,
+        {
+            type Rng = R;
+
+            #[inline]
+            fn reseed(&mut self, rng: R) {
+                self.rng = rng;
+            }
+        }
+    )+};
+}
+
+/// Kernel drawing its `i`-th replicate from the `i`-th replicate stream.
+///
+/// The streams are those of [`ReAt`], so sequential replicates (`re`,
+/// `re_lend`) are the same as `re_at(data, i)` and `re_par`, one for one.
+pub struct Streamed<S> {
+    strategy: S,
+    master: u64,
+    index: u64,
+}
+
+impl<S: Reseed> Streamed<S> {
+    /// `strategy` driven by the replicate streams of `rng`.
+    pub fn new(rng: &S::Rng, strategy: S) -> Self
+    where
+        S::Rng: Rng + Clone,
+    {
+        Self {
+            strategy,
+            master: master_seed(rng),
+            index: 0,
+        }
+    }
 }
 
-impl<T, R> ReStrategy<Vec<T>> for Bootstrap<R>
+impl<D, T, S> ReStrategy<D, T> for Streamed<S>
 where
-    R: Rng,
-    T: Copy,
+    S: ReStrategy<D, T> + Reseed,
 {
-    fn mutate(&mut self, data: &Vec<T>, buffer: &mut Vec<T>) {
-        let n = data.len();
-        for i in 0..n {
-           	let idx = self.rng.gen_range(0..n);
-           	unsafe {
-                *buffer.get_unchecked_mut(i) = *data.get_unchecked(idx);
+    #[inline]
+    fn mutate(&mut self, data: &D, buffer: &mut Vec<T>) -> bool {
+        self.strategy.reseed(S::Rng::seed_from_u64(stream_seed(self.master, self.index)));
+        self.index += 1;
+        self.strategy.mutate(data, buffer)
+    }
+
+    #[inline]
+    fn size_hint(&self, data: &D) -> (usize, Option<usize>) {
+        self.strategy.size_hint(data)
+    }
+}
+
+/// Replicate streams of `rng` as an iterator of per-replicate RNGs, for
+/// resamplers that draw replicates without a [`ReStrategy`].
+pub(crate) fn replicate_rngs<R: Rng + SeedableRng + Clone>(rng: &R) -> impl Iterator<Item = R> + use<R> {
+    let master = master_seed(rng);
+    (0..).map(move |index| R::seed_from_u64(stream_seed(master, index)))
+}
+
+/// Buffer-reusing resampling.
+///
+/// Every replicate is written into the same buffer and lent to the caller,
+/// so drawing B replicates costs one allocation instead of B.
+pub trait ReLend<D>: Re<D> {
+    /// Element type of a replicate
+    type Elem;
+    /// Kernel producing the replicates
+    type Strategy: ReStrategy<D, Self::Elem>;
+
+    /// Fresh kernel for resampling `data`.
+    fn strategy(&self, data: &D) -> Self::Strategy;
+
+    /// Lending iterator over replicates of `data`.
+    #[inline]
+    fn re_lend<'a>(&self, data: &'a D) -> ReIter<'a, Self::Strategy, D, Self::Elem> {
+        ReIter::new(self.strategy(data), data)
+    }
+
+    /// Call `f` on at most `n` replicates of `data`, reusing a single buffer.
+    fn for_each_resample<F>(&self, data: &D, n: usize, mut f: F)
+    where
+        F: FnMut(&Sample<Self::Elem>),
+    {
+        let mut iter = self.re_lend(data);
+        for _ in 0..n {
+            match iter.next_ref() {
+                Some(resample) => f(resample),
+                None => break,
             }
         }
     }
@@ -72,9 +431,206 @@ mod subsampling;
 mod flipper;
 mod block_bootstrap;
 mod wild_bootstrap;
+mod bayesian_bootstrap;
+mod stratified;
+mod cluster;
+mod parametric;
+mod smoothed;
+mod regression;
+mod sieve;
+mod poisson;
+mod indexed;
+mod balanced;
+mod restricted;
+mod cross_validation;
 
-pub use bootstrap::Bootstrap;
-pub use jackknife::Jackknife;
-pub use shuffle::Shuffle;
-pub use subsampling::{Subsample, SamplingMode};
+pub use bootstrap::{Bootstrap, BootstrapStrategy};
+pub use jackknife::{
+    DeleteJackknife, DeleteJackknifeStrategy, GroupedJackknife, GroupedJackknifeStrategy, Grouping,
+    Jackknife, JackknifeStrategy, RandomDeleteJackknife,
+};
+pub use shuffle::{Enumeration, ExactShuffle, ExactShuffleStrategy, Shuffle, ShuffleStrategy};
+pub use subsampling::{
+    Subsample, SamplingMode, SubsampleStrategy, fixed_ratio_policy, fixed_size_policy, log_policy,
+    sqrt_policy,
+};
 pub use flipper::*;
+pub use block_bootstrap::{BlockBootstrap, BlockBootstrapStrategy, BlockScheme, OptimalBlockLength, optimal_block_length};
+pub use wild_bootstrap::{WildBootstrap, WildBootstrapStrategy, WildSample, WeightDist};
+pub use bayesian_bootstrap::{BayesianBootstrap, FittedBayesian, Weighting};
+pub use stratified::{StratifiedBootstrap, StratifiedStrategy};
+pub use cluster::{ClusterBootstrap, ClusterStrategy, KeyedClusterBootstrap};
+pub use parametric::{Fitted, ParametricBootstrap, ParametricModel, ParametricStrategy};
+pub use smoothed::{Bandwidth, Kernel, SmoothedBootstrap, SmoothedStrategy};
+pub use regression::{FittedResiduals, PairsBootstrap, ResidualBootstrap};
+pub use sieve::{ArFit, FittedSieve, SieveBootstrap, SieveStrategy, fit_ar};
+pub use poisson::PoissonBootstrap;
+pub use indexed::{Gather, Indexed};
+pub use balanced::{AntitheticBootstrap, AntitheticStrategy, BalancedBootstrap, BalancedStrategy};
+pub use restricted::{Design, RestrictedShuffle, RestrictedShuffleStrategy};
+pub use cross_validation::{KFold, LeavePOut, RepeatedKFold, RollingOrigin, Split, StratifiedKFold};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mean, Paired, SE, Statistic};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn lent_replicates_match_owned_ones() {
+        let sample: Sample<f64> = (0..32).map(f64::from).collect();
+        let bootstrap = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(11));
+
+        let owned: Vec<Vec<f64>> = bootstrap.re(&sample).take(20).map(|r| r.data).collect();
+        let mut lent = Vec::new();
+        bootstrap.for_each_resample(&sample, 20, |r| lent.push(r.data.clone()));
+        assert_eq!(lent, owned);
+
+        // Exhaustible strategies stop early
+        let mut count = 0;
+        Jackknife::new().for_each_resample(&sample, usize::MAX, |_| count += 1);
+        assert_eq!(count, 32);
+
+        let se = SE::new(Mean, bootstrap, 200);
+        let buffered: f64 = se.compute_buffered(&sample);
+        let owned: f64 = se.compute(&sample);
+        assert!((buffered - owned).abs() < 1e-12);
+    }
+
+    /// `re` yields `re_at(data, 0), re_at(data, 1), …`
+    fn sequential_matches_random_access<D, S, K>(resampler: &S, data: &D, key: impl Fn(S::Item) -> K)
+    where
+        S: ReAt<D>,
+        K: PartialEq + std::fmt::Debug,
+    {
+        let sequential: Vec<K> = resampler.re(data).take(8).map(&key).collect();
+        let random: Vec<K> = (0..8).map(|index| key(resampler.re_at(data, index))).collect();
+        assert_eq!(sequential, random);
+    }
+
+    #[test]
+    fn sequential_replicates_come_from_the_replicate_streams() {
+        let sample: Sample<f64> = (0..24).map(|i| f64::from(i * i % 7)).collect();
+        let pairs: Paired<f64> = sample.data.iter().enumerate().map(|(i, &y)| (i as f64, y)).collect();
+        let keys: Vec<usize> = (0..24).map(|i| i / 4).collect();
+        let rng = || Xoshiro256PlusPlus::seed_from_u64(3);
+        let values = |replicate: Sample<f64>| replicate.data;
+
+        sequential_matches_random_access(&Bootstrap::new(rng()), &sample, values);
+        sequential_matches_random_access(&Shuffle::new(rng()), &sample, values);
+        sequential_matches_random_access(&Flipper::sign(rng()), &sample, values);
+        sequential_matches_random_access(&ClusterBootstrap::new(rng(), &keys).two_stage(), &sample, values);
+        sequential_matches_random_access(&StratifiedBootstrap::new(rng(), &keys), &sample, values);
+        sequential_matches_random_access(&RestrictedShuffle::cyclic(rng()), &sample, values);
+        let uniform = rand::distributions::Uniform::new(0.0, 1.0);
+        sequential_matches_random_access(&ParametricBootstrap::new(rng(), uniform), &sample, values);
+        sequential_matches_random_access(&SmoothedBootstrap::new(rng()), &sample, values);
+        sequential_matches_random_access(&SieveBootstrap::new(rng()), &sample, values);
+        sequential_matches_random_access(&SieveBootstrap::new(rng()).fitted(&sample), &sample, values);
+        sequential_matches_random_access(&Subsample::new(rng()), &sample, values);
+        sequential_matches_random_access(&RandomDeleteJackknife::new(rng(), 3), &sample, values);
+        sequential_matches_random_access(&AntitheticBootstrap::new(rng()), &sample, values);
+        sequential_matches_random_access(&BayesianBootstrap::new(rng()), &sample, |w| w.weights);
+        sequential_matches_random_access(&BayesianBootstrap::new(rng()).fitted(&sample), &sample, |w| w.weights);
+        sequential_matches_random_access(&PairsBootstrap::new(rng()), &pairs, |p| p.y);
+        sequential_matches_random_access(&ResidualBootstrap::new(rng()), &pairs, |p| p.y);
+        sequential_matches_random_access(&ResidualBootstrap::new(rng()).fitted(&pairs), &pairs, |p| p.y);
+        let keyed = (keys.clone(), sample.clone());
+        sequential_matches_random_access(&KeyedClusterBootstrap::new(rng()), &keyed, |(k, v)| (k, v.data));
+    }
+
+    #[test]
+    fn streams_are_reproducible_and_independent() {
+        let sample: Sample<f64> = (0..32).map(f64::from).collect();
+        let bootstrap = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(5));
+        let first = |resampler: &Bootstrap<Xoshiro256PlusPlus>| resampler.re(&sample).next().map(|r| r.data);
+
+        // A stream is regenerated from the seed, distinct streams differ
+        assert_eq!(first(&bootstrap.stream(1)), first(&bootstrap.stream(1)));
+        assert_ne!(first(&bootstrap.stream(1)), first(&bootstrap.stream(2)));
+        assert_ne!(first(&bootstrap.stream(1)), first(&bootstrap));
+        assert_ne!(first(&bootstrap.stream(1)), Some(bootstrap.re_at(&sample, 1).data));
+
+        // A seed restarts the replicates, whichever RNG state it replaces
+        let reseeded = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(9)).with_seed(5);
+        assert_eq!(first(&reseeded), first(&bootstrap));
+        assert_ne!(first(&bootstrap.clone().with_seed(6)), first(&bootstrap));
+
+        // Inner SEs of different outer replicates draw differently
+        let se = SE::new(Mean, bootstrap, 50);
+        let (one, two): (f64, f64) = (se.stream(1).compute(&sample), se.stream(2).compute(&sample));
+        assert!((one - two).abs() > 1e-9, "{one} vs {two}");
+    }
+
+    #[cfg(feature = "rayon")]
+    mod parallel {
+        use super::*;
+        use rayon::prelude::*;
+
+        fn sweep<S: ReAt<Sample<f64>>>(resampler: &S, sample: &Sample<f64>, b: usize) -> Vec<Vec<f64>>
+        where
+            S::Item: AsRef<[f64]>,
+        {
+            (0..b).map(|i| resampler.re_at(sample, i).as_ref().to_vec()).collect()
+        }
+
+        fn parallel<S>(resampler: &S, sample: &Sample<f64>, b: usize, threads: usize) -> Vec<Vec<f64>>
+        where
+            S: RePar<Sample<f64>> + Sync,
+            S::Item: AsRef<[f64]> + Send,
+        {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .expect("thread pool")
+                .install(|| {
+                    resampler
+                        .re_par(sample)
+                        .take(b)
+                        .map(|r| r.as_ref().to_vec())
+                        .collect()
+                })
+        }
+
+        #[test]
+        fn parallel_matches_sequential_for_any_thread_count() {
+            let sample: Sample<f64> = (0..64).map(f64::from).collect();
+            let rng = Xoshiro256PlusPlus::seed_from_u64(2024);
+
+            let bootstrap = Bootstrap::new(rng.clone());
+            let expected = sweep(&bootstrap, &sample, 100);
+            for threads in [1, 3, 8] {
+                assert_eq!(parallel(&bootstrap, &sample, 100, threads), expected);
+            }
+
+            let flipper = Flipper::sign(rng.clone());
+            assert_eq!(parallel(&flipper, &sample, 50, 4), sweep(&flipper, &sample, 50));
+
+            let jackknife = Jackknife::new();
+            assert_eq!(parallel(&jackknife, &sample, usize::MAX, 4), sweep(&jackknife, &sample, 64));
+        }
+
+        #[test]
+        fn parallel_computations_match_sequential_ones() {
+            use crate::{MeanTest, StudentizedBootstrap, VarianceTest};
+
+            let sample: Sample<f64> = (0..40).map(|i| f64::from(i * i % 11) / 3.0).collect();
+            let bootstrap = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(8));
+
+            let se = SE::new(Mean, bootstrap.clone(), 300);
+            let (sequential, parallel): (f64, f64) = (se.compute(&sample), se.compute_par(&sample));
+            assert!((sequential - parallel).abs() < 1e-12, "{sequential} vs {parallel}");
+
+            let studentized = StudentizedBootstrap::new(Mean, SE::new(Mean, bootstrap.clone(), 50), bootstrap, 200, 0.9);
+            let (sequential, parallel) = (studentized.compute(&sample), studentized.compute_par(&sample));
+            assert!((sequential.lower - parallel.lower).abs() < 1e-12, "{sequential:?} vs {parallel:?}");
+            assert!((sequential.upper - parallel.upper).abs() < 1e-12, "{sequential:?} vs {parallel:?}");
+
+            let data = sample.data.clone();
+            let mean_test = MeanTest::new(2.0, 500).with_seed(4);
+            assert_eq!(mean_test.compute(&data), mean_test.compute_par(&data));
+            let variance_test = VarianceTest::new(1.0, 500).with_seed(4);
+            assert_eq!(variance_test.compute(&data), variance_test.compute_par(&data));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use rand::{Rng, SeedableRng};
+use rand::distributions::Distribution;
+use crate::Sample;
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, replicate_rng};
+
+/// Probability model fitted to the observed sample.
+///
+/// Implemented by [`Fitted`] (a distribution fixed in advance) and by every
+/// closure `Fn(&Sample<T>) -> D` that refits a distribution to the data.
+pub trait ParametricModel<T> {
+    /// Fitted law replicates are drawn from
+    type Law: Distribution<T>;
+
+    /// Fit the model to `sample`.
+    fn fit(&self, sample: &Sample<T>) -> Self::Law;
+}
+
+/// Distribution fixed in advance, regardless of the data.
+#[derive(Clone, Copy, Debug)]
+pub struct Fitted<D>(pub D);
+
+impl<T, D: Distribution<T> + Clone> ParametricModel<T> for Fitted<D> {
+    type Law = D;
+
+    fn fit(&self, _sample: &Sample<T>) -> D {
+        self.0.clone()
+    }
+}
+
+impl<T, D, F> ParametricModel<T> for F
+where
+    D: Distribution<T>,
+    F: Fn(&Sample<T>) -> D,
+{
+    type Law = D;
+
+    fn fit(&self, sample: &Sample<T>) -> D {
+        self(sample)
+    }
+}
+
+/// Parametric bootstrap: replicates of size `n` drawn from a fitted model.
+///
+/// Any `rand` distribution works as a model, including every continuous and
+/// discrete `statrs` distribution. With [`refit`](Self::refit) the model is
+/// estimated from the sample once per [`re`](Re::re) call:
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_xoshiro::Xoshiro256PlusPlus;
+/// use statrs::distribution::Normal;
+/// use zima::{Mean, ParametricBootstrap, SE, Sample, Statistic, Variance};
+///
+/// let sample: Sample<f64> = vec![0.3, -1.1, 0.8, 1.9, -0.4, 0.2].into_iter().collect();
+/// let normal = ParametricBootstrap::refit(Xoshiro256PlusPlus::seed_from_u64(1), |s: &Sample<f64>| {
+///     let sd = Variance::default().compute(s).sqrt();
+///     Normal::new(Mean.compute(s), sd).expect("positive spread")
+/// });
+/// let se: f64 = SE::new(Mean, normal, 1000).compute(&sample);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ParametricBootstrap<M, R: Rng> {
+    pub rng: R,
+    pub model: M,
+}
+
+impl<D, R: Rng> ParametricBootstrap<Fitted<D>, R> {
+    /// Draw replicates from a fixed distribution.
+    pub fn new(rng: R, distribution: D) -> Self {
+        Self {
+            rng,
+            model: Fitted(distribution),
+        }
+    }
+}
+
+impl<F, R: Rng> ParametricBootstrap<F, R> {
+    /// Fit the distribution with `fit` to every sample being resampled.
+    pub fn refit(rng: R, fit: F) -> Self {
+        Self { rng, model: fit }
+    }
+}
+
+impl<T, M: ParametricModel<T>, R: Rng + SeedableRng + Clone> Re<Sample<T>> for ParametricBootstrap<M, R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T, M: ParametricModel<T>, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for ParametricBootstrap<M, R> {
+    type Elem = T;
+    type Strategy = Streamed<ParametricStrategy<M::Law, R>>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        Streamed::new(&self.rng, ParametricStrategy::new(self.rng.clone(), self.model.fit(sample)))
+    }
+}
+
+impl<T, M, R> ReAt<Sample<T>> for ParametricBootstrap<M, R>
+where
+    M: ParametricModel<T>,
+    R: Rng + SeedableRng + Clone,
+{
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let strategy = ParametricStrategy::new(replicate_rng(&self.rng, index), self.model.fit(sample));
+        ReIter::new(strategy, sample)
+            .next()
+            .expect("parametric iterator is unbounded")
+    }
+}
+
+/// Draws `n` independent observations from a fitted law.
+pub struct ParametricStrategy<L, R: Rng> {
+    rng: R,
+    law: L,
+}
+
+impl<L, R: Rng> ParametricStrategy<L, R> {
+    pub fn new(rng: R, law: L) -> Self {
+        Self { rng, law }
+    }
+}
+
+impl_reseed!(ParametricStrategy<L, R>);
+
+impl<T, L: Distribution<T>, R: Rng> ReStrategy<Sample<T>, T> for ParametricStrategy<L, R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        buffer.clear();
+        buffer.extend((&self.law).sample_iter(&mut self.rng).take(sample.len()));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Interval, KSTest, Mean, SE, Statistic, StudentizedBootstrap, Variance};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+    use statrs::distribution::{Normal, Poisson};
+
+    #[test]
+    fn normal_model_reproduces_textbook_se() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(2);
+        let normal = Normal::new(3.0, 2.0).expect("valid normal");
+        let sample: Sample<f64> = (0..100).map(|_| normal.sample(&mut rng)).collect();
+
+        let refit = |s: &Sample<f64>| {
+            Normal::new(Mean.compute(s), Variance::default().compute(s).sqrt()).expect("positive spread")
+        };
+        let resampler = ParametricBootstrap::refit(Xoshiro256PlusPlus::seed_from_u64(5), refit);
+
+        let se: f64 = SE::new(Mean, resampler.clone(), 4000).compute(&sample);
+        let textbook = Variance::default().compute(&sample).sqrt() / 10.0;
+        assert!((se / textbook - 1.0).abs() < 0.05, "{se} vs {textbook}");
+
+        // Composes with the studentized interval
+        let inner = SE::new(Mean, resampler.clone(), 50);
+        let interval: Interval<f64> = StudentizedBootstrap::new(Mean, inner, resampler, 500, 0.95).compute(&sample);
+        assert!(interval.contains(&3.0), "{interval:?}");
+
+        // Any statistic, including hypothesis tests, runs on the replicates
+        let rejections = ParametricBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(9), Normal::standard())
+            .re(&sample)
+            .take(400)
+            .filter(|replicate| KSTest.compute(replicate).p_value < 0.05)
+            .count();
+        assert!(rejections < 40, "{rejections} of 400 rejected at 5%");
+    }
+
+    #[test]
+    fn discrete_models_draw_counts() {
+        let sample: Sample<u64> = vec![0, 3, 1, 2, 4, 2].into_iter().collect();
+        let poisson = Poisson::new(2.0).expect("valid poisson");
+        let resampler = ParametricBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(1), poisson);
+
+        let first = resampler.re(&sample).next().expect("unbounded");
+        assert_eq!(first.len(), sample.len());
+        assert_eq!(resampler.re_at(&sample, 3).data, resampler.re_at(&sample, 3).data);
+    }
+}
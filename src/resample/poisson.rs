@@ -0,0 +1,214 @@
+use num_traits::{Float, FromPrimitive};
+use rand::{Rng, SeedableRng};
+use crate::{Accumulator, Mergeable, Statistic, Variance};
+use super::{Stream, split_rng};
+
+/// Streaming bootstrap with Poisson(1) weights (Hanley & MacGibbon, 2006).
+///
+/// Multinomial resampling needs the whole sample to draw `n` indices. Giving
+/// every observation an independent Poisson(1) weight in each replicate instead
+/// yields the same bootstrap distribution asymptotically, and lets each
+/// observation be processed once, on arrival. B running accumulators (plus one
+/// for the full-sample estimate) are the only state, so the memory footprint
+/// does not grow with `n`.
+///
+/// Chunks may be processed by separate instances — ideally seeded with
+/// independent streams — and combined with [`merge`](Self::merge).
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_xoshiro::Xoshiro256PlusPlus;
+/// use zima::{Mean, PoissonBootstrap};
+///
+/// let mut bootstrap = PoissonBootstrap::new(&Mean, Xoshiro256PlusPlus::seed_from_u64(1), 500);
+/// for chunk in [[1.0, 2.5, 0.3], [4.1, 2.2, 1.7]] {
+///     bootstrap.extend(chunk);
+/// }
+/// let (mean, se) = (bootstrap.estimate(), bootstrap.se());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PoissonBootstrap<A, R: Rng> {
+    rng: R,
+    estimate: A,
+    replicates: Vec<A>,
+}
+
+impl<A: Clone, R: Rng> PoissonBootstrap<A, R> {
+    /// `replicates` accumulators of `statistic`.
+    pub fn new<T, S>(statistic: &S, rng: R, replicates: usize) -> Self
+    where
+        S: Mergeable<T, Accumulator = A>,
+    {
+        let empty = statistic.accumulator();
+        Self {
+            rng,
+            replicates: vec![empty.clone(); replicates],
+            estimate: empty,
+        }
+    }
+}
+
+impl<A, R: Rng> PoissonBootstrap<A, R> {
+    /// Number of bootstrap replicates B
+    pub fn len(&self) -> usize {
+        self.replicates.len()
+    }
+
+    /// Check if no replicates are kept
+    pub fn is_empty(&self) -> bool {
+        self.replicates.is_empty()
+    }
+
+    /// Feed one observation to the estimate and every replicate.
+    pub fn push<T>(&mut self, x: T)
+    where
+        T: Float + FromPrimitive,
+        A: Accumulator<T>,
+    {
+        self.estimate.push(x, T::one());
+        for replicate in &mut self.replicates {
+            let count = poisson_one(&mut self.rng);
+            if count > 0 {
+                replicate.push(x, T::from_u32(count).expect("count fits in float"));
+            }
+        }
+    }
+
+    /// Feed a chunk of observations.
+    pub fn extend<T, I>(&mut self, chunk: I)
+    where
+        T: Float + FromPrimitive,
+        A: Accumulator<T>,
+        I: IntoIterator<Item = T>,
+    {
+        for x in chunk {
+            self.push(x);
+        }
+    }
+
+    /// Absorb the state of an instance that processed other observations.
+    ///
+    /// Instances for different chunks need independent weights: create them
+    /// with [`Stream::stream`] rather than by cloning.
+    ///
+    /// # Panics
+    /// Panics if the numbers of replicates differ.
+    pub fn merge<T>(&mut self, other: &Self)
+    where
+        A: Accumulator<T>,
+    {
+        assert_eq!(self.len(), other.len(), "Merged bootstraps must keep the same number of replicates");
+        self.estimate.merge(&other.estimate);
+        for (replicate, partial) in self.replicates.iter_mut().zip(&other.replicates) {
+            replicate.merge(partial);
+        }
+    }
+
+    /// Statistic over all observations seen so far.
+    pub fn estimate<T>(&self) -> T
+    where
+        A: Accumulator<T>,
+    {
+        self.estimate.value()
+    }
+
+    /// Replicate values θ*₁, …, θ*_B (NaN while a replicate has no weight).
+    pub fn replicates<T>(&self) -> Vec<T>
+    where
+        A: Accumulator<T>,
+    {
+        self.replicates.iter().map(Accumulator::value).collect()
+    }
+
+    /// Bootstrap standard error: the standard deviation of the defined replicates.
+    pub fn se<T>(&self) -> T
+    where
+        T: Float + FromPrimitive,
+        A: Accumulator<T>,
+    {
+        let values: Vec<T> = self
+            .replicates()
+            .into_iter()
+            .filter(|value: &T| !value.is_nan())
+            .collect();
+        Variance::default().compute(&values).sqrt()
+    }
+}
+
+impl<A: Clone, R: Rng + SeedableRng + Clone> Stream for PoissonBootstrap<A, R> {
+    fn stream(&self, index: usize) -> Self {
+        Self {
+            rng: split_rng(&self.rng, index),
+            estimate: self.estimate.clone(),
+            replicates: self.replicates.clone(),
+        }
+    }
+
+    fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = R::seed_from_u64(seed);
+        self
+    }
+}
+
+/// Poisson(1) draw by CDF inversion; the mean number of steps is 2.
+#[inline]
+fn poisson_one<R: Rng + ?Sized>(rng: &mut R) -> u32 {
+    let u: f64 = rng.r#gen();
+    let mut k = 0;
+    let mut p = (-1.0_f64).exp();
+    let mut cdf = p;
+    while u > cdf && k < 32 {
+        k += 1;
+        p /= f64::from(k);
+        cdf += p;
+    }
+    k
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Mean, SE, Sample};
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    fn data() -> Sample<f64> {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(4);
+        (0..4000).map(|_| rng.r#gen::<f64>().powi(3) * 10.0).collect()
+    }
+
+    #[test]
+    fn chunked_streams_match_in_memory_bootstrap() {
+        let data = data();
+        let (first, second) = data.data.split_at(2000);
+
+        let mut left = PoissonBootstrap::new(&Mean, Xoshiro256PlusPlus::seed_from_u64(1), 1000);
+        let mut right = PoissonBootstrap::new(&Mean, Xoshiro256PlusPlus::seed_from_u64(2), 1000);
+        for chunk in first.chunks(300) {
+            left.extend(chunk.iter().copied());
+        }
+        right.extend(second.iter().copied());
+        left.merge(&right);
+
+        let mean: f64 = left.estimate();
+        assert!((mean - Mean.compute(&data)).abs() < 1e-9);
+
+        let streamed: f64 = left.se();
+        let in_memory: f64 = SE::new(Mean, Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(3)), 1000).compute(&data);
+        assert!((streamed / in_memory - 1.0).abs() < 0.1, "{streamed} vs {in_memory}");
+    }
+
+    #[test]
+    fn variance_se_matches_in_memory_bootstrap() {
+        let data = data();
+        let mut streaming = PoissonBootstrap::new(&Variance::default(), Xoshiro256PlusPlus::seed_from_u64(5), 1000);
+        streaming.extend(data.data.iter().copied());
+
+        let variance: f64 = streaming.estimate();
+        assert!((variance - Variance::default().compute(&data)).abs() < 1e-9);
+
+        let streamed: f64 = streaming.se();
+        let in_memory: f64 = SE::new(Variance::default(), Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(6)), 1000)
+            .compute(&data);
+        assert!((streamed / in_memory - 1.0).abs() < 0.1, "{streamed} vs {in_memory}");
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::Arc;
+use num_traits::{Float, FromPrimitive};
+use rand::{Rng, SeedableRng};
+use crate::{LeastSquares, Paired, Statistic};
+use super::{Re, ReAt, replicate_rng, replicate_rngs};
+
+/// Pairs (case) bootstrap: draws `(xᵢ, yᵢ)` rows with replacement.
+///
+/// Makes no assumption about the error structure, so it stays valid under
+/// heteroskedasticity and a misspecified mean; the design changes between
+/// replicates.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PairsBootstrap<R: Rng> {
+    pub rng: R,
+}
+
+impl<R: Rng> PairsBootstrap<R> {
+    pub fn new(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+/// `n` rows of `data` drawn with replacement.
+fn draw_pairs<T: Copy, R: Rng>(rng: &mut R, data: &Paired<T>) -> Paired<T> {
+    let n = data.len();
+    let (x, y): (Vec<T>, Vec<T>) = (0..n)
+        .filter_map(|_| {
+            let i = rng.gen_range(0..n);
+            data.x.get(i).copied().zip(data.y.get(i).copied())
+        })
+        .unzip();
+    Paired { x: x.into(), y }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> Re<Paired<T>> for PairsBootstrap<R> {
+    type Item = Paired<T>;
+
+    fn re(&self, data: &Paired<T>) -> impl Iterator<Item = Self::Item> {
+        replicate_rngs(&self.rng).map(move |mut rng| draw_pairs(&mut rng, data))
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReAt<Paired<T>> for PairsBootstrap<R> {
+    fn re_at(&self, data: &Paired<T>, index: usize) -> Self::Item {
+        draw_pairs(&mut replicate_rng(&self.rng, index), data)
+    }
+}
+
+/// Residual bootstrap: resamples residuals around the least-squares fit.
+///
+/// The design `x` is kept fixed and shared between replicates; responses are
+/// `y*ᵢ = ŷᵢ + e*ᵢ` with `e*ᵢ` drawn with replacement from the residuals. Assumes
+/// homoskedastic, exchangeable errors. OLS residuals are too small on average;
+/// [`rescaled`](Self::rescaled) inflates them by `√(n/(n-2))`.
+///
+/// The line is refitted on every `re` call and, through `re_at`, for every
+/// replicate. For random access or parallel resampling of one data set, fit
+/// once with [`fitted`](Self::fitted).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResidualBootstrap<R: Rng> {
+    pub rng: R,
+    pub rescale: bool,
+}
+
+impl<R: Rng> ResidualBootstrap<R> {
+    pub fn new(rng: R) -> Self {
+        Self { rng, rescale: false }
+    }
+
+    /// Inflate residuals by `√(n/(n-2))` to undo the fit's variance shrinkage
+    pub fn rescaled(mut self) -> Self {
+        self.rescale = true;
+        self
+    }
+
+    /// Resampler drawing around the least-squares fit of `data`: fitted values
+    /// and (optionally rescaled) residuals computed once, shared by every replicate.
+    pub fn fitted<T: Float + FromPrimitive>(&self, data: &Paired<T>) -> FittedResiduals<T, R>
+    where
+        R: Clone,
+    {
+        let line = LeastSquares.compute(data);
+        let fitted: Arc<[T]> = data.x.iter().map(|&x| line.predict(x)).collect();
+        let mut residuals = line.residuals(data);
+
+        let n = data.len();
+        if self.rescale && n > 2 {
+            let factor = T::from_f64((n as f64 / (n - 2) as f64).sqrt()).expect("factor fits in float");
+            residuals.iter_mut().for_each(|e| *e = *e * factor);
+        }
+        FittedResiduals {
+            rng: self.rng.clone(),
+            fitted,
+            residuals: residuals.into(),
+        }
+    }
+}
+
+/// Responses `ŷᵢ + e*ᵢ` with residuals drawn with replacement.
+fn draw_responses<T: Float, R: Rng>(rng: &mut R, fitted: &[T], residuals: &[T]) -> Vec<T> {
+    let n = residuals.len();
+    fitted
+        .iter()
+        .map(|&y_hat| y_hat + residuals.get(rng.gen_range(0..n)).copied().unwrap_or_else(T::nan))
+        .collect()
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> Re<Paired<T>> for ResidualBootstrap<R> {
+    type Item = Paired<T>;
+
+    fn re(&self, data: &Paired<T>) -> impl Iterator<Item = Self::Item> {
+        let fitted = self.fitted(data);
+        replicate_rngs(&self.rng).map(move |mut rng| fitted.draw(&mut rng, data))
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> ReAt<Paired<T>> for ResidualBootstrap<R> {
+    fn re_at(&self, data: &Paired<T>, index: usize) -> Self::Item {
+        self.fitted(data).re_at(data, index)
+    }
+}
+
+/// [`ResidualBootstrap`] with its least-squares fit computed once.
+///
+/// Applies to the data it was fitted to (same design `x`) and panics on data of
+/// another length. Draws match those of the `ResidualBootstrap` it was fitted by.
+#[derive(Clone, Debug)]
+pub struct FittedResiduals<T, R: Rng> {
+    pub rng: R,
+    fitted: Arc<[T]>,
+    residuals: Arc<[T]>,
+}
+
+impl<T: Float, R: Rng> FittedResiduals<T, R> {
+    /// Replicate of `data` with responses drawn by `rng`.
+    fn draw<S: Rng>(&self, rng: &mut S, data: &Paired<T>) -> Paired<T> {
+        assert_eq!(data.len(), self.fitted.len(), "One fitted value per observation");
+        Paired {
+            x: Arc::clone(&data.x),
+            y: draw_responses(rng, &self.fitted, &self.residuals),
+        }
+    }
+}
+
+impl<T: Float, R: Rng + SeedableRng + Clone> Re<Paired<T>> for FittedResiduals<T, R> {
+    type Item = Paired<T>;
+
+    fn re(&self, data: &Paired<T>) -> impl Iterator<Item = Self::Item> {
+        replicate_rngs(&self.rng).map(move |mut rng| self.draw(&mut rng, data))
+    }
+}
+
+impl<T: Float, R: Rng + SeedableRng + Clone> ReAt<Paired<T>> for FittedResiduals<T, R> {
+    fn re_at(&self, data: &Paired<T>, index: usize) -> Self::Item {
+        self.draw(&mut replicate_rng(&self.rng, index), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CDF, Intercept, Interval, QuantileInterval, SE, Slope, StudentizedBootstrap};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    /// `y = 1 + 2x + ε` with uniform errors of variance 1/3
+    fn data() -> Paired<f64> {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(6);
+        (0..60)
+            .map(|i| {
+                let x = f64::from(i) / 10.0;
+                (x, 1.0 + 2.0 * x + rng.gen_range(-1.0..1.0))
+            })
+            .collect()
+    }
+
+    /// Textbook OLS standard error of the slope: `σ̂ / √Sxx`
+    fn textbook_slope_se(data: &Paired<f64>) -> f64 {
+        let line = LeastSquares.compute(data);
+        let rss: f64 = line.residuals(data).iter().map(|e| e * e).sum();
+        let mean_x = data.x.iter().sum::<f64>() / data.len() as f64;
+        let sxx: f64 = data.x.iter().map(|x| (x - mean_x).powi(2)).sum();
+        (rss / (data.len() - 2) as f64 / sxx).sqrt()
+    }
+
+    #[test]
+    fn slope_se_matches_textbook_formula() {
+        let data = data();
+        let textbook = textbook_slope_se(&data);
+        let rng = Xoshiro256PlusPlus::seed_from_u64(1);
+
+        let pairs: f64 = SE::new(Slope, PairsBootstrap::new(rng.clone()), 4000).compute(&data);
+        let residual: f64 = SE::new(Slope, ResidualBootstrap::new(rng).rescaled(), 4000).compute(&data);
+
+        assert!((pairs / textbook - 1.0).abs() < 0.15, "{pairs} vs {textbook}");
+        assert!((residual / textbook - 1.0).abs() < 0.05, "{residual} vs {textbook}");
+    }
+
+    #[test]
+    fn percentile_intervals_cover_true_coefficients() {
+        let data = data();
+        let resampler = ResidualBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(2)).rescaled();
+        let replicates: Vec<(f64, f64)> = resampler
+            .re(&data)
+            .take(2000)
+            .map(|replicate| (Intercept.compute(&replicate), Slope.compute(&replicate)))
+            .collect();
+
+        let interval = |values: Vec<f64>| {
+            let (lower, upper) = QuantileInterval::percentile(0.95).compute(&CDF.compute(&values));
+            Interval::new(lower, upper)
+        };
+        let intercepts = interval(replicates.iter().map(|r| r.0).collect());
+        let slopes = interval(replicates.iter().map(|r| r.1).collect());
+
+        assert!(intercepts.contains(&1.0), "{intercepts:?}");
+        assert!(slopes.contains(&2.0), "{slopes:?}");
+
+        // Studentized interval with pairs resampling at both levels
+        let pairs = PairsBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(3));
+        let studentized: Interval<f64> =
+            StudentizedBootstrap::new(Slope, SE::new(Slope, pairs.clone(), 50), pairs, 400, 0.95).compute(&data);
+        assert!(studentized.contains(&2.0), "{studentized:?}");
+
+        // The design is shared, not copied
+        let replicate = resampler.re_at(&data, 0);
+        assert!(Arc::ptr_eq(&replicate.x, &data.x));
+
+        // Fitting once draws the same replicates
+        let fitted = resampler.fitted(&data);
+        for (index, expected) in resampler.re(&data).take(3).enumerate() {
+            assert_eq!(fitted.re_at(&data, index).y, expected.y);
+        }
+    }
+}
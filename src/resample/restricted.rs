@@ -0,0 +1,200 @@
+use std::hash::Hash;
+use std::sync::Arc;
+use rand::{Rng, SeedableRng};
+use rand::seq::SliceRandom;
+use crate::Sample;
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, group_indices, replicate_rng};
+
+/// Exchangeability structure of a design: which rearrangements of the
+/// observations are equally likely under the null hypothesis.
+#[derive(Clone, Debug)]
+pub enum Design {
+    /// Any permutation (completely randomized design)
+    Free,
+    /// Permutations within each block or stratum only (randomized blocks)
+    Within(Arc<[Vec<usize>]>),
+    /// Cyclic shifts `xₜ ↦ xₜ₊ₗ mod n` of a series
+    Cyclic,
+    /// Whole plots permuted as units, subplot order kept (split-plot designs)
+    WholePlots(Arc<[Vec<usize>]>),
+}
+
+/// Permutation resampler restricted to the rearrangements a [`Design`] allows.
+///
+/// Unrestricted shuffling breaks the structure of blocked, stratified or
+/// serially dependent data and makes permutation tests anti-conservative;
+/// this resampler only produces rearrangements that are exchangeable under the
+/// design, so it can drive [`PermutationTest`](crate::PermutationTest) in place of
+/// `Shuffle`.
+#[derive(Clone, Debug)]
+pub struct RestrictedShuffle<R: Rng> {
+    pub rng: R,
+    design: Design,
+}
+
+impl<R: Rng> RestrictedShuffle<R> {
+    pub fn new(rng: R, design: Design) -> Self {
+        if let Design::WholePlots(plots) = &design {
+            let size = plots.first().map_or(0, Vec::len);
+            assert!(plots.iter().all(|plot| plot.len() == size), "Whole plots must have equal sizes");
+        }
+        Self { rng, design }
+    }
+
+    /// Permute within the blocks `blocks[i]` of observation `i`.
+    pub fn within<K: Eq + Hash>(rng: R, blocks: &[K]) -> Self {
+        Self::new(rng, Design::Within(group_indices(blocks).into()))
+    }
+
+    /// Random cyclic shifts of the whole series.
+    pub fn cyclic(rng: R) -> Self {
+        Self::new(rng, Design::Cyclic)
+    }
+
+    /// Permute the whole plots `plots[i]` of observation `i`.
+    ///
+    /// # Panics
+    /// Panics if the plots differ in size.
+    pub fn whole_plots<K: Eq + Hash>(rng: R, plots: &[K]) -> Self {
+        Self::new(rng, Design::WholePlots(group_indices(plots).into()))
+    }
+
+    pub fn design(&self) -> &Design {
+        &self.design
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> Re<Sample<T>> for RestrictedShuffle<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for RestrictedShuffle<R> {
+    type Elem = T;
+    type Strategy = Streamed<RestrictedShuffleStrategy<R>>;
+
+    fn strategy(&self, _sample: &Sample<T>) -> Self::Strategy {
+        Streamed::new(&self.rng, RestrictedShuffleStrategy::new(self.rng.clone(), self.design.clone()))
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for RestrictedShuffle<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let strategy = RestrictedShuffleStrategy::new(replicate_rng(&self.rng, index), self.design.clone());
+        ReIter::new(strategy, sample)
+            .next()
+            .expect("restricted shuffle iterator is unbounded")
+    }
+}
+
+/// Draws a design-respecting permutation `π` and yields `x_{π(i)}`.
+pub struct RestrictedShuffleStrategy<R: Rng> {
+    rng: R,
+    design: Design,
+    /// Source position of every target position
+    sources: Vec<usize>,
+    /// Scratch permutation of blocks or plots
+    order: Vec<usize>,
+}
+
+impl<R: Rng> RestrictedShuffleStrategy<R> {
+    pub fn new(rng: R, design: Design) -> Self {
+        Self {
+            rng,
+            design,
+            sources: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    /// Fill `sources` with the next permutation of `n` positions.
+    fn permute(&mut self, n: usize) {
+        self.sources.clear();
+        self.sources.extend(0..n);
+
+        match &self.design {
+            Design::Free => self.sources.shuffle(&mut self.rng),
+            Design::Within(blocks) => {
+                for members in blocks.iter() {
+                    self.order.clear();
+                    self.order.extend_from_slice(members);
+                    self.order.shuffle(&mut self.rng);
+                    for (&target, &source) in members.iter().zip(&self.order) {
+                        if let Some(slot) = self.sources.get_mut(target) {
+                            *slot = source;
+                        }
+                    }
+                }
+            }
+            Design::Cyclic => {
+                if n > 0 {
+                    let lag = self.rng.gen_range(0..n);
+                    self.sources.rotate_left(lag);
+                }
+            }
+            Design::WholePlots(plots) => {
+                self.order.clear();
+                self.order.extend(0..plots.len());
+                self.order.shuffle(&mut self.rng);
+                for (plot, &source) in plots.iter().zip(&self.order) {
+                    let Some(source) = plots.get(source) else { continue };
+                    for (&target, &from) in plot.iter().zip(source) {
+                        if let Some(slot) = self.sources.get_mut(target) {
+                            *slot = from;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl_reseed!(RestrictedShuffleStrategy<R>);
+
+impl<T: Copy, R: Rng> ReStrategy<Sample<T>, T> for RestrictedShuffleStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        self.permute(data.len());
+
+        buffer.clear();
+        buffer.extend(self.sources.iter().filter_map(|&i| data.get(i).copied()));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn rearrangements_respect_the_design() {
+        let rng = Xoshiro256PlusPlus::seed_from_u64(4);
+        let sample: Sample<f64> = (0..12).map(f64::from).collect();
+
+        // Blocks {0..4}, {4..8}, {8..12}: values never leave their block
+        let blocks: Vec<usize> = (0..12).map(|i| i / 4).collect();
+        for replicate in RestrictedShuffle::within(rng.clone(), &blocks).re(&sample).take(20) {
+            assert!(replicate.data.iter().zip(&blocks).all(|(&x, &b)| x as usize / 4 == b));
+        }
+
+        // Cyclic shifts keep every neighbour pair (up to the wrap-around)
+        for replicate in RestrictedShuffle::cyclic(rng.clone()).re(&sample).take(20) {
+            let steps = replicate.data.windows(2).filter(|w| matches!(w, [a, b] if (b - a - 1.0).abs() < 1e-12));
+            assert!(steps.count() >= 10);
+        }
+
+        // Plots of three move as units with their internal order
+        let plots: Vec<usize> = (0..12).map(|i| i / 3).collect();
+        let resampler = RestrictedShuffle::whole_plots(rng, &plots);
+        for replicate in resampler.re(&sample).take(20).chain([resampler.re_at(&sample, 3)]) {
+            for plot in replicate.data.chunks(3) {
+                let consecutive = |a: &f64, b: &f64| (b - a - 1.0).abs() < 1e-12;
+                assert!(matches!(plot, [a, b, c] if *a as usize % 3 == 0 && consecutive(a, b) && consecutive(b, c)));
+            }
+        }
+    }
+}
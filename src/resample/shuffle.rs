@@ -1,6 +1,6 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use crate::Sample;
-use super::Re;
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, replicate_rng};
 
 #[derive(Clone, Copy, Default)]
 pub struct Shuffle<R: Rng> {
@@ -13,51 +13,63 @@ impl<R: Rng> Shuffle<R> {
     }
 }
 
-impl<T: Copy, R: Rng + Clone> Re<Sample<T>> for Shuffle<R> {
+impl<T: Copy, R: Rng + SeedableRng + Clone> Re<Sample<T>> for Shuffle<R> {
     type Item = Sample<T>;
 
     fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
-        Box::new(ShuffleIter::new(&sample.data, self.rng.clone()))
+        self.re_lend(sample)
     }
 }
 
-pub struct ShuffleIter<'a, T, R: Rng> {
-    data: &'a [T],
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for Shuffle<R> {
+    type Elem = T;
+    type Strategy = Streamed<ShuffleStrategy<R>>;
+
+    fn strategy(&self, _sample: &Sample<T>) -> Self::Strategy {
+        Streamed::new(&self.rng, ShuffleStrategy::new(self.rng.clone()))
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for Shuffle<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        ReIter::new(ShuffleStrategy::new(replicate_rng(&self.rng, index)), sample)
+            .next()
+            .expect("shuffle iterator is unbounded")
+    }
+}
+
+/// Uniformly random permutation of the observations.
+pub struct ShuffleStrategy<R: Rng> {
     rng: R,
-    buffer: Vec<T>,
 }
 
-impl<'a, T: Copy, R: Rng> ShuffleIter<'a, T, R> {
-    fn new(data: &'a [T], rng: R) -> Self {
-        // Pre-allocate buffer with exact capacity to avoid reallocations
-        Self {
-            buffer: Vec::with_capacity(data.len()),
-            data,
-            rng,
-        }
+impl<R: Rng> ShuffleStrategy<R> {
+    pub fn new(rng: R) -> Self {
+        Self { rng }
     }
 }
 
-impl<'a, T: Copy, R: Rng> Iterator for ShuffleIter<'a, T, R> {
-    type Item = Sample<T>;
+impl_reseed!(ShuffleStrategy<R>);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let n = self.data.len();
+impl<T: Copy, R: Rng> ReStrategy<Sample<T>, T> for ShuffleStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        let n = data.len();
 
         // Reuse buffer allocation with zero-copy semantics
         unsafe {
             // Ensure capacity (defensive check; should never trigger with fixed-size input)
-            if self.buffer.capacity() < n {
-                self.buffer.reserve_exact(n);
+            if buffer.capacity() < n {
+                buffer.reserve_exact(n);
             }
             // Set length and perform raw memcpy (bypasses bounds checks)
-            self.buffer.set_len(n);
-            std::ptr::copy_nonoverlapping(self.data.as_ptr(), self.buffer.as_mut_ptr(), n);
+            buffer.set_len(n);
+            std::ptr::copy_nonoverlapping(data.as_ptr(), buffer.as_mut_ptr(), n);
         }
 
         // Optimized Fisher-Yates shuffle (in-place, no allocations)
         if n > 1 {
-            let ptr = self.buffer.as_mut_ptr();
+            let ptr = buffer.as_mut_ptr();
             // Iterate from last index down to 1 (avoids unnecessary swap at index 0)
             for i in (1..n).rev() {
                 // Inclusive range [0, i] - critical for uniform distribution
@@ -71,7 +83,282 @@ impl<'a, T: Copy, R: Rng> Iterator for ShuffleIter<'a, T, R> {
             }
         }
 
-        // Transfer ownership to Sample while preserving buffer capacity
-        Some(Sample::new(std::mem::take(&mut self.buffer)))
+        true
+    }
+}
+
+/// Configurations enumerated by [`ExactShuffle`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Enumeration {
+    /// All `n!` orderings of the observations
+    #[default]
+    Permutations,
+    /// All `C(n, first)` splits into a first group of size `first` and the rest;
+    /// both groups keep the original relative order
+    Splits { first: usize },
+}
+
+/// Exhaustive shuffling: yields every permutation (or every distinct split into
+/// two groups) exactly once, in lexicographic order of the chosen indices.
+///
+/// Replicate 0 is always the data itself. For two-sample tests `Splits` is the
+/// cheaper choice, since orderings within a group do not change the statistic.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExactShuffle {
+    pub enumeration: Enumeration,
+}
+
+impl ExactShuffle {
+    /// All `n!` permutations
+    pub fn permutations() -> Self {
+        Self {
+            enumeration: Enumeration::Permutations,
+        }
+    }
+
+    /// All `C(n, first)` group splits
+    pub fn splits(first: usize) -> Self {
+        Self {
+            enumeration: Enumeration::Splits { first },
+        }
+    }
+
+    /// Number of configurations for `n` observations (`usize::MAX` on overflow)
+    pub fn configurations(&self, n: usize) -> usize {
+        match self.enumeration {
+            Enumeration::Permutations => (2..=n).try_fold(1usize, |acc, k| acc.checked_mul(k)),
+            Enumeration::Splits { first } => binomial(n, first),
+        }
+        .unwrap_or(usize::MAX)
+    }
+
+    /// Index order of configuration `rank`
+    pub(crate) fn unrank(&self, n: usize, mut rank: usize) -> Vec<usize> {
+        match self.enumeration {
+            Enumeration::Permutations => {
+                // Factorial number system (Lehmer code)
+                let mut available: Vec<usize> = (0..n).collect();
+                let mut order = Vec::with_capacity(n);
+                for remaining in (1..=n).rev() {
+                    let block = (1..remaining).try_fold(1usize, |acc, k| acc.checked_mul(k));
+                    let pick = block.map_or(0, |block| {
+                        let pick = rank / block;
+                        rank %= block;
+                        pick
+                    });
+                    order.push(available.remove(pick.min(remaining - 1)));
+                }
+                order
+            }
+            Enumeration::Splits { first } => {
+                // Combinatorial number system, lexicographic
+                let first = first.min(n);
+                let mut chosen = Vec::with_capacity(first);
+                let mut candidate = 0;
+                while chosen.len() < first {
+                    let rest = first - chosen.len() - 1;
+                    let with_candidate = binomial(n - candidate - 1, rest).unwrap_or(usize::MAX);
+                    if rank < with_candidate {
+                        chosen.push(candidate);
+                    } else {
+                        rank -= with_candidate;
+                    }
+                    candidate += 1;
+                }
+                split_order(&chosen, n)
+            }
+        }
+    }
+}
+
+/// `C(n, k)`, `None` on overflow
+fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    (0..k).try_fold(1u128, |acc, i| {
+        acc.checked_mul((n - i) as u128).map(|v| v / (i as u128 + 1))
+    })
+    .and_then(|v| usize::try_from(v).ok())
+}
+
+/// Chosen indices followed by the complement, both ascending.
+fn split_order(chosen: &[usize], n: usize) -> Vec<usize> {
+    let mut order = chosen.to_vec();
+    let mut next = chosen.iter().peekable();
+    for i in 0..n {
+        if next.peek() == Some(&&i) {
+            next.next();
+        } else {
+            order.push(i);
+        }
+    }
+    order
+}
+
+impl<T: Copy> Re<Sample<T>> for ExactShuffle {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Copy> ReLend<Sample<T>> for ExactShuffle {
+    type Elem = T;
+    type Strategy = ExactShuffleStrategy;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        ExactShuffleStrategy::new(self.enumeration, sample.len())
+    }
+}
+
+impl<T: Copy> ReAt<Sample<T>> for ExactShuffle {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        self.unrank(sample.len(), index)
+            .into_iter()
+            .filter_map(|i| sample.data.get(i).copied())
+            .collect()
+    }
+
+    #[inline]
+    fn count(&self, sample: &Sample<T>) -> usize {
+        self.configurations(sample.len())
+    }
+}
+
+/// Steps through index orders with next-permutation / next-combination.
+pub struct ExactShuffleStrategy {
+    enumeration: Enumeration,
+    n: usize,
+    /// Permutation of `0..n`, or the chosen indices of a split
+    state: Vec<usize>,
+    started: bool,
+    exhausted: bool,
+    remaining: usize,
+}
+
+impl ExactShuffleStrategy {
+    pub fn new(enumeration: Enumeration, n: usize) -> Self {
+        let state = match enumeration {
+            Enumeration::Permutations => (0..n).collect(),
+            Enumeration::Splits { first } => (0..first.min(n)).collect(),
+        };
+        Self {
+            enumeration,
+            n,
+            state,
+            started: false,
+            exhausted: false,
+            remaining: ExactShuffle { enumeration }.configurations(n),
+        }
+    }
+
+    /// Advance `state` to the next configuration; `false` after the last one.
+    fn advance(&mut self) -> bool {
+        let n = self.n;
+        match self.enumeration {
+            Enumeration::Permutations => {
+                let state = &mut self.state;
+                let Some(pivot) = (1..state.len()).rev().find(|&i| state.get(i - 1) < state.get(i)) else {
+                    return false;
+                };
+                let pivot = pivot - 1;
+                let pivot_value = state.get(pivot).copied();
+                let successor = (pivot + 1..state.len())
+                    .rev()
+                    .find(|&j| state.get(j).copied() > pivot_value)
+                    .expect("pivot has a larger successor");
+                state.swap(pivot, successor);
+                if let Some(tail) = state.get_mut(pivot + 1..) {
+                    tail.reverse();
+                }
+                true
+            }
+            Enumeration::Splits { .. } => {
+                let state = &mut self.state;
+                let k = state.len();
+                let Some(i) = (0..k).rev().find(|&i| state.get(i).is_some_and(|&c| c < n - k + i)) else {
+                    return false;
+                };
+                let start = state.get(i).copied().unwrap_or_default() + 1;
+                for (offset, slot) in state.iter_mut().skip(i).enumerate() {
+                    *slot = start + offset;
+                }
+                true
+            }
+        }
+    }
+}
+
+impl<T: Copy> ReStrategy<Sample<T>, T> for ExactShuffleStrategy {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        if self.exhausted {
+            return false;
+        }
+        if self.started && !self.advance() {
+            self.exhausted = true;
+            return false;
+        }
+        self.started = true;
+        self.remaining = self.remaining.saturating_sub(1);
+
+        let data = &sample.data;
+        buffer.clear();
+        match self.enumeration {
+            Enumeration::Permutations => {
+                buffer.extend(self.state.iter().filter_map(|&i| data.get(i)).copied());
+            }
+            Enumeration::Splits { .. } => {
+                buffer.extend(
+                    split_order(&self.state, self.n)
+                        .into_iter()
+                        .filter_map(|i| data.get(i))
+                        .copied(),
+                );
+            }
+        }
+        true
+    }
+
+    #[inline]
+    fn size_hint(&self, _sample: &Sample<T>) -> (usize, Option<usize>) {
+        if self.exhausted {
+            (0, Some(0))
+        } else {
+            (self.remaining, Some(self.remaining))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enumerates_every_configuration_once() {
+        let sample: Sample<u32> = (1..=5).collect();
+
+        let permutations: Vec<Vec<u32>> = ExactShuffle::permutations().re(&sample).map(|s| s.data).collect();
+        assert_eq!(permutations.len(), 120);
+        let mut unique = permutations.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 120);
+
+        let splits = ExactShuffle::splits(2);
+        let enumerated: Vec<Vec<u32>> = splits.re(&sample).map(|s| s.data).collect();
+        assert_eq!(enumerated.len(), 10);
+        assert_eq!(enumerated.first(), Some(&vec![1, 2, 3, 4, 5]));
+        assert_eq!(enumerated.last(), Some(&vec![4, 5, 1, 2, 3]));
+
+        // Random access agrees with sequential enumeration
+        for (rank, expected) in permutations.iter().enumerate() {
+            assert_eq!(&ExactShuffle::permutations().re_at(&sample, rank).data, expected);
+        }
+        for (rank, expected) in enumerated.iter().enumerate() {
+            assert_eq!(&splits.re_at(&sample, rank).data, expected);
+        }
     }
 }
@@ -0,0 +1,407 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use num_traits::{Float, FromPrimitive, ToPrimitive};
+use rand::{Rng, SeedableRng};
+use crate::Sample;
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, replicate_rng};
+
+/// Autoregressive model `xₜ - μ = Σ φⱼ(xₜ₋ⱼ - μ) + εₜ` fitted to a series.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArFit {
+    /// Series mean μ
+    pub mean: f64,
+    /// Coefficients `φ₁, …, φₚ`
+    pub coefficients: Vec<f64>,
+    /// Yule–Walker innovation variance σ²ₚ
+    pub innovation_variance: f64,
+}
+
+impl ArFit {
+    /// Order `p` of the model
+    pub fn order(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    /// Centered in-sample innovations `ε̂ₜ - mean(ε̂)` for `t = p, …, n-1`.
+    pub fn innovations(&self, data: &[f64]) -> Vec<f64> {
+        let p = self.order();
+        let mut residuals: Vec<f64> = data
+            .windows(p + 1)
+            .map(|window| {
+                // window = [xₜ₋ₚ, …, xₜ₋₁, xₜ]
+                let prediction: f64 = window
+                    .iter()
+                    .rev()
+                    .skip(1)
+                    .zip(&self.coefficients)
+                    .map(|(x, phi)| phi * (x - self.mean))
+                    .sum();
+                window.last().map_or(f64::NAN, |x| x - self.mean - prediction)
+            })
+            .collect();
+
+        let center = residuals.iter().sum::<f64>() / residuals.len().max(1) as f64;
+        residuals.iter_mut().for_each(|e| *e -= center);
+        residuals
+    }
+}
+
+/// Yule–Walker AR fit with the order chosen by AIC.
+///
+/// Levinson–Durbin recursion yields the fits of every order `p ≤ max_order` at
+/// once; the one minimizing `AIC(p) = n·ln σ²ₚ + 2p` is returned. Yule–Walker
+/// estimates are always causal, so regenerated series never explode.
+pub fn fit_ar<T: Copy + ToPrimitive>(data: &[T], max_order: usize) -> ArFit {
+    let fits = yule_walker(data, max_order);
+    let n = data.len() as f64;
+    fits.into_iter()
+        .min_by(|a, b| {
+            let aic = |fit: &ArFit| n * fit.innovation_variance.ln() + 2.0 * fit.order() as f64;
+            aic(a).total_cmp(&aic(b))
+        })
+        .expect("order zero is always fitted")
+}
+
+/// Yule–Walker fits of orders `0, …, max_order` (Levinson–Durbin).
+fn yule_walker<T: Copy + ToPrimitive>(data: &[T], max_order: usize) -> Vec<ArFit> {
+    let values: Vec<f64> = data.iter().map(|x| x.to_f64().unwrap_or(f64::NAN)).collect();
+    let n = values.len();
+    let n_f = n.max(1) as f64;
+    let mean = values.iter().sum::<f64>() / n_f;
+
+    let max_order = max_order.min(n.saturating_sub(1));
+    let gamma: Vec<f64> = (0..=max_order)
+        .map(|lag| {
+            values
+                .iter()
+                .zip(values.iter().skip(lag))
+                .map(|(a, b)| (a - mean) * (b - mean))
+                .sum::<f64>()
+                / n_f
+        })
+        .collect();
+
+    let gamma0 = gamma.first().copied().unwrap_or(0.0);
+    let mut fits = vec![ArFit {
+        mean,
+        coefficients: Vec::new(),
+        innovation_variance: gamma0,
+    }];
+    if !gamma0.is_finite() || gamma0 <= 0.0 {
+        return fits;
+    }
+
+    let mut phi: Vec<f64> = Vec::with_capacity(max_order);
+    let mut variance = gamma0;
+    for (k, &gamma_k) in gamma.iter().enumerate().skip(1) {
+        // Partial autocorrelation φₖₖ
+        let projection: f64 = phi
+            .iter()
+            .zip(gamma.iter().take(k).skip(1).rev())
+            .map(|(p, g)| p * g)
+            .sum();
+        let reflection = (gamma_k - projection) / variance;
+
+        let previous = phi.clone();
+        for (current, mirrored) in phi.iter_mut().zip(previous.iter().rev()) {
+            *current -= reflection * mirrored;
+        }
+        phi.push(reflection);
+        variance *= 1.0 - reflection * reflection;
+
+        if !(variance.is_finite() && variance > 0.0) {
+            break;
+        }
+        fits.push(ArFit {
+            mean,
+            coefficients: phi.clone(),
+            innovation_variance: variance,
+        });
+    }
+    fits
+}
+
+/// Sieve bootstrap for stationary series (Bühlmann, 1997).
+///
+/// Approximates the series by an AR(p) whose order grows with `n` (chosen by
+/// AIC up to `⌊10·log₁₀ n⌋` unless fixed), resamples the centered innovations
+/// with replacement and regenerates each replicate through the fitted
+/// recursion after a burn-in. Needs no block length, which makes it the usual
+/// fallback for long linear-process series where block tuning is unreliable.
+///
+/// The model is refitted on every `re` call and, through `re_at`, for every
+/// replicate. For random access or parallel resampling of one series, fit once
+/// with [`fitted`](Self::fitted).
+#[derive(Clone, Copy, Debug)]
+pub struct SieveBootstrap<R: Rng> {
+    pub rng: R,
+    /// Fixed AR order (disables AIC selection)
+    pub order: Option<usize>,
+    /// Largest order considered by AIC
+    pub max_order: Option<usize>,
+    /// Discarded start-up steps of every regenerated series
+    pub burn_in: usize,
+}
+
+impl<R: Rng> SieveBootstrap<R> {
+    /// AIC-selected order, burn-in of 100 steps.
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            order: None,
+            max_order: None,
+            burn_in: 100,
+        }
+    }
+
+    /// Use a fixed AR order.
+    pub fn with_order(mut self, order: usize) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Largest order searched by AIC.
+    pub fn with_max_order(mut self, max_order: usize) -> Self {
+        self.max_order = Some(max_order);
+        self
+    }
+
+    pub fn with_burn_in(mut self, burn_in: usize) -> Self {
+        self.burn_in = burn_in;
+        self
+    }
+
+    /// AR model the replicates are generated from.
+    pub fn fit<T: Copy + ToPrimitive>(&self, data: &[T]) -> ArFit {
+        match self.order {
+            Some(order) => {
+                let mut fits = yule_walker(data, order);
+                fits.pop().expect("order zero is always fitted")
+            }
+            None => {
+                let default = (10.0 * (data.len().max(1) as f64).log10()).floor() as usize;
+                fit_ar(data, self.max_order.unwrap_or(default))
+            }
+        }
+    }
+
+    /// Resampler regenerating replicates from the model fitted to `sample`,
+    /// shared by all of them.
+    pub fn fitted<T: Copy + ToPrimitive>(&self, sample: &Sample<T>) -> FittedSieve<R>
+    where
+        R: Clone,
+    {
+        let values: Vec<f64> = sample.data.iter().map(|x| x.to_f64().unwrap_or(f64::NAN)).collect();
+        let model = self.fit(&values);
+        let innovations = model.innovations(&values).into();
+        FittedSieve {
+            rng: self.rng.clone(),
+            burn_in: self.burn_in,
+            model: Arc::new(model),
+            innovations,
+        }
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> Re<Sample<T>> for SieveBootstrap<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for SieveBootstrap<R> {
+    type Elem = T;
+    type Strategy = Streamed<SieveStrategy<R>>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        self.fitted(sample).strategy(sample)
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for SieveBootstrap<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        self.fitted(sample).re_at(sample, index)
+    }
+}
+
+/// [`SieveBootstrap`] with its AR model fitted once.
+///
+/// Replicates of any requested length are regenerated from the stored model
+/// and innovations, so `re_at` and `re_par` cost no refit. Draws match those of
+/// the `SieveBootstrap` it was fitted by, on the series it was fitted to.
+#[derive(Clone, Debug)]
+pub struct FittedSieve<R: Rng> {
+    pub rng: R,
+    pub burn_in: usize,
+    model: Arc<ArFit>,
+    innovations: Arc<[f64]>,
+}
+
+impl<R: Rng> FittedSieve<R> {
+    /// The fitted AR model
+    pub fn model(&self) -> &ArFit {
+        &self.model
+    }
+
+    fn strategy_with<S: Rng>(&self, rng: S) -> SieveStrategy<S> {
+        SieveStrategy::new(rng, Arc::clone(&self.model), Arc::clone(&self.innovations), self.burn_in)
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> Re<Sample<T>> for FittedSieve<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for FittedSieve<R> {
+    type Elem = T;
+    type Strategy = Streamed<SieveStrategy<R>>;
+
+    fn strategy(&self, _sample: &Sample<T>) -> Self::Strategy {
+        Streamed::new(&self.rng, self.strategy_with(self.rng.clone()))
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for FittedSieve<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        ReIter::new(self.strategy_with(replicate_rng(&self.rng, index)), sample)
+            .next()
+            .expect("sieve iterator is unbounded")
+    }
+}
+
+/// Regenerates a series of length `n` through the fitted AR recursion.
+pub struct SieveStrategy<R: Rng> {
+    rng: R,
+    model: Arc<ArFit>,
+    innovations: Arc<[f64]>,
+    burn_in: usize,
+    /// Most recent centered values first
+    history: VecDeque<f64>,
+}
+
+impl<R: Rng> SieveStrategy<R> {
+    pub fn new(rng: R, model: Arc<ArFit>, innovations: Arc<[f64]>, burn_in: usize) -> Self {
+        let history = VecDeque::with_capacity(model.order() + 1);
+        Self {
+            rng,
+            model,
+            innovations,
+            burn_in,
+            history,
+        }
+    }
+
+    /// Next centered value `Σ φⱼ y*ₜ₋ⱼ + ε*ₜ`.
+    fn step(&mut self) -> f64 {
+        let m = self.innovations.len();
+        let shock = if m == 0 {
+            0.0
+        } else {
+            self.innovations.get(self.rng.gen_range(0..m)).copied().unwrap_or(0.0)
+        };
+        let value = self
+            .history
+            .iter()
+            .zip(&self.model.coefficients)
+            .map(|(y, phi)| phi * y)
+            .sum::<f64>()
+            + shock;
+
+        self.history.push_front(value);
+        self.history.truncate(self.model.order());
+        value
+    }
+}
+
+impl_reseed!(SieveStrategy<R>);
+
+impl<T: Float + FromPrimitive, R: Rng> ReStrategy<Sample<T>, T> for SieveStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let n = sample.len();
+        buffer.clear();
+        buffer.reserve_exact(n);
+
+        // Start every replicate from the stationary mean
+        self.history.clear();
+        self.history.resize(self.model.order(), 0.0);
+        for _ in 0..self.burn_in {
+            self.step();
+        }
+        for _ in 0..n {
+            let value = self.model.mean + self.step();
+            buffer.push(T::from_f64(value).unwrap_or_else(T::nan));
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Mean, SE, Statistic};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    /// AR(1) series around 5 with uniform innovations of variance 1/12
+    fn ar1(phi: f64, n: usize) -> Sample<f64> {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(11);
+        let mut x = 0.0;
+        (0..n)
+            .map(|_| {
+                x = phi * x + (rng.r#gen::<f64>() - 0.5);
+                5.0 + x
+            })
+            .collect()
+    }
+
+    fn lag1_autocorrelation(data: &[f64]) -> f64 {
+        let fit = yule_walker(data, 1);
+        fit.last().and_then(|f| f.coefficients.first().copied()).unwrap_or(f64::NAN)
+    }
+
+    #[test]
+    fn aic_recovers_ar1_order() {
+        let sample = ar1(0.6, 3000);
+        let fit = SieveBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(0)).fit(&sample.data);
+        // AIC tends to overfit slightly, but not by much
+        assert!((1..=5).contains(&fit.order()), "order {}", fit.order());
+        let phi = fit.coefficients.first().copied().unwrap_or(f64::NAN);
+        assert!((phi - 0.6).abs() < 0.05, "φ̂ = {phi}");
+        assert!((fit.innovation_variance - 1.0 / 12.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn replicates_keep_dependence_and_long_run_se() {
+        let phi = 0.6;
+        let sample = ar1(phi, 2000);
+        let resampler = SieveBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(3));
+
+        for replicate in resampler.re(&sample).take(5) {
+            assert_eq!(replicate.len(), sample.len());
+            assert!((lag1_autocorrelation(&replicate.data) - phi).abs() < 0.1);
+        }
+
+        // Long-run SE of the mean: σε / ((1 - φ)·√n)
+        let long_run = (1.0_f64 / 12.0).sqrt() / (1.0 - phi) / (sample.len() as f64).sqrt();
+        let sieve: f64 = SE::new(Mean, resampler.clone(), 1000).compute(&sample);
+        let iid: f64 = SE::new(Mean, Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(3)), 1000).compute(&sample);
+
+        assert!((sieve / long_run - 1.0).abs() < 0.15, "{sieve} vs {long_run}");
+        assert!(iid < 0.7 * long_run);
+
+        // A fitted sieve shares one model between replicates and draws the same ones
+        let fitted = resampler.fitted(&sample);
+        assert_eq!(fitted.model(), &resampler.fit(&sample.data));
+        for index in [0, 7] {
+            assert_eq!(fitted.re_at(&sample, index).data, resampler.re_at(&sample, index).data);
+        }
+        let shared: f64 = SE::new(Mean, fitted, 1000).compute(&sample);
+        assert!((shared - sieve).abs() < 1e-12, "{shared} vs {sieve}");
+    }
+}
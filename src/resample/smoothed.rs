@@ -0,0 +1,274 @@
+use num_traits::{Float, FromPrimitive};
+use rand::{Rng, SeedableRng};
+use rand::distributions::Distribution;
+use statrs::distribution::Normal;
+use crate::{CDF, Quantile, Sample, Statistic, Variance};
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, replicate_rng};
+
+/// Smoothing kernel of the jitter `h·ε` added to every resampled point
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Kernel {
+    /// Standard normal: ε ~ N(0,1)
+    #[default]
+    Gaussian,
+    /// Epanechnikov: density ¾(1-ε²) on [-1, 1], Var[ε] = 1/5
+    Epanechnikov,
+    /// Uniform on [-1, 1], Var[ε] = 1/3
+    Uniform,
+}
+
+impl Kernel {
+    /// Draw a single kernel deviate.
+    #[inline]
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            Self::Gaussian => Normal::new(0.0, 1.0)
+                .expect("Valid N(0,1) distribution")
+                .sample(rng),
+            Self::Epanechnikov => {
+                // Devroye (1986): median-like pick among three uniforms
+                let u1: f64 = rng.gen_range(-1.0..=1.0);
+                let u2: f64 = rng.gen_range(-1.0..=1.0);
+                let u3: f64 = rng.gen_range(-1.0..=1.0);
+                if u3.abs() >= u2.abs() && u3.abs() >= u1.abs() { u2 } else { u3 }
+            }
+            Self::Uniform => rng.gen_range(-1.0..=1.0),
+        }
+    }
+
+    /// Variance of a kernel deviate
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        match self {
+            Self::Gaussian => 1.0,
+            Self::Epanechnikov => 0.2,
+            Self::Uniform => 1.0 / 3.0,
+        }
+    }
+}
+
+/// Bandwidth `h` of the smoothing kernel
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Bandwidth {
+    /// Fixed bandwidth
+    Fixed(f64),
+    /// Silverman's rule: `0.9·min(σ̂, IQR/1.34)·n^{-1/5}`
+    #[default]
+    Silverman,
+    /// Scott's rule: `1.06·σ̂·n^{-1/5}`
+    Scott,
+}
+
+impl Bandwidth {
+    /// Bandwidth for `data`; rules give NaN for empty data.
+    pub fn select(&self, data: &[f64]) -> f64 {
+        let n = data.len() as f64;
+        match self {
+            Self::Fixed(h) => *h,
+            _ if data.is_empty() => f64::NAN,
+            Self::Silverman => {
+                let sd = Variance::default().compute(&data).sqrt();
+                let ecdf = CDF.compute(&data);
+                let iqr: f64 = Quantile::new(0.75).compute(&ecdf) - Quantile::new(0.25).compute(&ecdf);
+                // A degenerate IQR (ties, tiny samples) must not zero the bandwidth
+                let spread = if iqr > 0.0 { sd.min(iqr / 1.34) } else { sd };
+                0.9 * spread * n.powf(-0.2)
+            }
+            Self::Scott => 1.06 * Variance::default().compute(&data).sqrt() * n.powf(-0.2),
+        }
+    }
+}
+
+/// Smoothed bootstrap: ordinary resampling plus kernel jitter.
+///
+/// Every replicate point is `x*ᵢ = x_{Iᵢ} + h·εᵢ`, i.e. a draw from the kernel
+/// density estimate of the data. This breaks the ties of `Bootstrap` and gives
+/// quantile statistics a continuous replicate distribution.
+///
+/// The jitter inflates the variance by `h²·Var[ε]`; with
+/// [`shrink`](Self::shrink) points are pulled towards the mean,
+/// ```text
+/// x*ᵢ = x̄ + (x_{Iᵢ} - x̄ + h·εᵢ) / √(1 + h²·Var[ε]/σ̂²)
+/// ```
+/// so the replicates keep the sample variance σ̂² (Silverman, 1986, §6.4).
+#[derive(Clone, Copy, Debug)]
+pub struct SmoothedBootstrap<R: Rng> {
+    pub rng: R,
+    pub kernel: Kernel,
+    pub bandwidth: Bandwidth,
+    pub shrink: bool,
+}
+
+impl<R: Rng> SmoothedBootstrap<R> {
+    /// Gaussian kernel with Silverman's bandwidth, no shrinking.
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            kernel: Kernel::default(),
+            bandwidth: Bandwidth::default(),
+            shrink: false,
+        }
+    }
+
+    pub fn with_kernel(mut self, kernel: Kernel) -> Self {
+        self.kernel = kernel;
+        self
+    }
+
+    pub fn with_bandwidth(mut self, bandwidth: Bandwidth) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    /// Rescale replicates to preserve the sample variance
+    pub fn shrink(mut self) -> Self {
+        self.shrink = true;
+        self
+    }
+
+    fn strategy_with<T: Float, S: Rng>(&self, rng: S, sample: &Sample<T>) -> SmoothedStrategy<S> {
+        let data: Vec<f64> = sample.data.iter().filter_map(|x| x.to_f64()).collect();
+        let h = self.bandwidth.select(&data);
+        let n = data.len().max(1) as f64;
+        let mean = data.iter().sum::<f64>() / n;
+
+        let scale = if self.shrink {
+            let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+            if variance > 0.0 {
+                (1.0 + h * h * self.kernel.variance() / variance).sqrt().recip()
+            } else {
+                1.0
+            }
+        } else {
+            1.0
+        };
+
+        SmoothedStrategy::new(rng, self.kernel, h, mean, scale)
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> Re<Sample<T>> for SmoothedBootstrap<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for SmoothedBootstrap<R> {
+    type Elem = T;
+    type Strategy = Streamed<SmoothedStrategy<R>>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        Streamed::new(&self.rng, self.strategy_with(self.rng.clone(), sample))
+    }
+}
+
+impl<T: Float + FromPrimitive, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for SmoothedBootstrap<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        ReIter::new(self.strategy_with(replicate_rng(&self.rng, index), sample), sample)
+            .next()
+            .expect("smoothed bootstrap iterator is unbounded")
+    }
+}
+
+/// Draws `n` observations with replacement and jitters them with kernel noise.
+pub struct SmoothedStrategy<R: Rng> {
+    rng: R,
+    kernel: Kernel,
+    bandwidth: f64,
+    mean: f64,
+    scale: f64,
+}
+
+impl<R: Rng> SmoothedStrategy<R> {
+    /// `scale` is the shrink factor towards `mean` (1 for no shrinking).
+    pub fn new(rng: R, kernel: Kernel, bandwidth: f64, mean: f64, scale: f64) -> Self {
+        Self {
+            rng,
+            kernel,
+            bandwidth,
+            mean,
+            scale,
+        }
+    }
+}
+
+impl_reseed!(SmoothedStrategy<R>);
+
+impl<T: Float + FromPrimitive, R: Rng> ReStrategy<Sample<T>, T> for SmoothedStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        let n = data.len();
+        buffer.clear();
+        buffer.reserve_exact(n);
+
+        for _ in 0..n {
+            let x = data
+                .get(self.rng.gen_range(0..n))
+                .and_then(|x| x.to_f64())
+                .unwrap_or(f64::NAN);
+            let jittered = x - self.mean + self.bandwidth * self.kernel.sample(&mut self.rng);
+            buffer.push(T::from_f64(self.mean + jittered * self.scale).unwrap_or_else(T::nan));
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Mean, SE};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    fn sample() -> Sample<f64> {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(17);
+        (0..40).map(|_| rng.r#gen::<f64>() * 4.0).collect()
+    }
+
+    #[test]
+    fn smoothed_medians_are_not_tied() {
+        let sample = sample();
+        let median = |r: &Sample<f64>| Quantile::new(0.5).compute(&CDF.compute(r));
+        let distinct = |mut medians: Vec<f64>| {
+            medians.sort_by(f64::total_cmp);
+            medians.dedup_by(|a, b| (*a - *b).abs() < 1e-12);
+            medians.len()
+        };
+
+        let rng = Xoshiro256PlusPlus::seed_from_u64(2);
+        let plain: Vec<f64> = Bootstrap::new(rng.clone()).re(&sample).take(500).map(|r| median(&r)).collect();
+        let smooth: Vec<f64> = SmoothedBootstrap::new(rng).re(&sample).take(500).map(|r| median(&r)).collect();
+
+        assert!(distinct(plain) < 60);
+        assert_eq!(distinct(smooth), 500);
+
+        // An empty sample gives empty replicates, not a panic
+        let empty = Sample::new(Vec::<f64>::new());
+        assert!(Bandwidth::Silverman.select(&[]).is_nan());
+        let se: f64 = SE::new(Mean, SmoothedBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(4)), 10).compute(&empty);
+        assert!(se.is_nan());
+    }
+
+    #[test]
+    fn shrinking_preserves_variance() {
+        let sample = sample();
+        let population = Variance { ddof: 0 }.compute(&sample);
+        let pooled_variance = |resampler: SmoothedBootstrap<Xoshiro256PlusPlus>| {
+            let pooled: Sample<f64> = resampler.re(&sample).take(500).flat_map(|r| r.data).collect();
+            (Mean.compute(&pooled), Variance { ddof: 0 }.compute(&pooled))
+        };
+
+        let resampler = SmoothedBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(3))
+            .with_kernel(Kernel::Epanechnikov)
+            .with_bandwidth(Bandwidth::Fixed(1.0));
+
+        let (_, inflated) = pooled_variance(resampler.clone());
+        assert!((inflated - population - 0.2).abs() < 0.05, "{inflated} vs {population} + 0.2");
+
+        let (mean, shrunk) = pooled_variance(resampler.shrink());
+        assert!((shrunk / population - 1.0).abs() < 0.03, "{shrunk} vs {population}");
+        assert!((mean - Mean.compute(&sample)).abs() < 0.02);
+    }
+}
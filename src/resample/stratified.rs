@@ -0,0 +1,154 @@
+use std::hash::Hash;
+use std::sync::Arc;
+use rand::{Rng, SeedableRng};
+use crate::Sample;
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, group_indices, replicate_rng};
+
+/// Stratified bootstrap: resamples with replacement within each stratum.
+///
+/// Stratum sizes are kept, and every observation of a replicate stays at a
+/// position of its own stratum, so the grouping keys remain valid for the
+/// replicate (nested SE estimates can reuse the same resampler).
+#[derive(Clone, Debug)]
+pub struct StratifiedBootstrap<R: Rng> {
+    pub rng: R,
+    strata: Arc<[Vec<usize>]>,
+}
+
+impl<R: Rng> StratifiedBootstrap<R> {
+    /// `strata[i]` is the stratum (batch, run, patient…) of observation `i`.
+    pub fn new<K: Eq + Hash>(rng: R, strata: &[K]) -> Self {
+        Self {
+            rng,
+            strata: group_indices(strata).into(),
+        }
+    }
+
+    /// Number of observations the resampler was built for
+    pub fn len(&self) -> usize {
+        self.strata.iter().map(Vec::len).sum()
+    }
+
+    /// Check if the resampler was built for no observations
+    pub fn is_empty(&self) -> bool {
+        self.strata.is_empty()
+    }
+
+    /// Number of strata
+    pub fn n_strata(&self) -> usize {
+        self.strata.len()
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> Re<Sample<T>> for StratifiedBootstrap<R> {
+    type Item = Sample<T>;
+
+    fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReLend<Sample<T>> for StratifiedBootstrap<R> {
+    type Elem = T;
+    type Strategy = Streamed<StratifiedStrategy<R>>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
+        assert_eq!(sample.len(), self.len(), "One stratum key per observation");
+        Streamed::new(&self.rng, StratifiedStrategy::new(self.rng.clone(), Arc::clone(&self.strata)))
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone> ReAt<Sample<T>> for StratifiedBootstrap<R> {
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        assert_eq!(sample.len(), self.len(), "One stratum key per observation");
+        let strategy = StratifiedStrategy::new(replicate_rng(&self.rng, index), Arc::clone(&self.strata));
+        ReIter::new(strategy, sample)
+            .next()
+            .expect("stratified iterator is unbounded")
+    }
+}
+
+/// Draws `nₕ` observations with replacement from every stratum `h`.
+pub struct StratifiedStrategy<R: Rng> {
+    rng: R,
+    strata: Arc<[Vec<usize>]>,
+}
+
+impl<R: Rng> StratifiedStrategy<R> {
+    pub fn new(rng: R, strata: Arc<[Vec<usize>]>) -> Self {
+        Self { rng, strata }
+    }
+}
+
+impl_reseed!(StratifiedStrategy<R>);
+
+impl<T: Copy, R: Rng> ReStrategy<Sample<T>, T> for StratifiedStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        buffer.clear();
+        buffer.extend_from_slice(data);
+
+        for members in self.strata.iter() {
+            let size = members.len();
+            for &target in members {
+                let source = *members
+                    .get(self.rng.gen_range(0..size))
+                    .expect("index drawn within stratum");
+                if let (Some(slot), Some(&x)) = (buffer.get_mut(target), data.get(source)) {
+                    *slot = x;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Interval, Mean, SE, Statistic, StudentizedBootstrap};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn observations_stay_in_their_stratum() {
+        // Stratum A holds small values, stratum B large ones, interleaved
+        let keys = ['a', 'b', 'a', 'b', 'b', 'a', 'b'];
+        let sample: Sample<f64> = [1.0, 100.0, 2.0, 200.0, 300.0, 3.0, 400.0].into_iter().collect();
+        let resampler = StratifiedBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(9), &keys);
+        assert_eq!(resampler.n_strata(), 2);
+
+        for resample in resampler.re(&sample).take(50) {
+            for (&key, &x) in keys.iter().zip(&resample.data) {
+                assert_eq!(key == 'a', x < 10.0);
+            }
+        }
+    }
+
+    #[test]
+    fn stratification_removes_between_strata_variance() {
+        let keys: Vec<usize> = (0..40).map(|i| i % 2).collect();
+        let sample: Sample<f64> = (0..40)
+            .map(|i| if i % 2 == 0 { 0.0 } else { 10.0 } + f64::from(i % 3))
+            .collect();
+        let rng = Xoshiro256PlusPlus::seed_from_u64(1);
+
+        let stratified: f64 = SE::new(Mean, StratifiedBootstrap::new(rng.clone(), &keys), 2000).compute(&sample);
+        let iid: f64 = SE::new(Mean, crate::Bootstrap::new(rng), 2000).compute(&sample);
+        assert!(stratified < 0.5 * iid, "{stratified} vs {iid}");
+    }
+
+    #[test]
+    fn plugs_into_studentized_interval() {
+        let keys: Vec<usize> = (0..30).map(|i| i % 3).collect();
+        let sample: Sample<f64> = (0..30).map(|i| f64::from(i % 3) * 5.0 + f64::from(i % 7)).collect();
+        let resampler = StratifiedBootstrap::new(Xoshiro256PlusPlus::seed_from_u64(2), &keys);
+
+        // Replicates keep the stratum layout, so the inner SE reuses the same keys
+        let se = SE::new(Mean, resampler.clone(), 50);
+        let interval: Interval<f64> = StudentizedBootstrap::new(Mean, se, resampler, 200, 0.9).compute(&sample);
+        let mean: f64 = Mean.compute(&sample);
+        assert!(interval.is_valid());
+        assert!(interval.contains(&mean));
+    }
+}
@@ -1,6 +1,6 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use crate::Sample;
-use super::Re;
+use super::{Re, ReAt, ReIter, ReLend, ReStrategy, Streamed, replicate_rng};
 
 /// Subsampling strategy selector
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -52,68 +52,93 @@ where
     }
 }
 
-impl<T: Copy, R: Rng + Clone, F> Re<Sample<T>> for Subsample<R, F>
+impl<R: Rng> Subsample<R, fn(usize) -> usize> {
+    /// m-out-of-n bootstrap: `m` draws with replacement from the `n` observations.
+    ///
+    /// Consistent for extremes and boundary parameters where the ordinary
+    /// bootstrap (m = n) is not, provided m → ∞ and m/n → 0. See
+    /// `MOutOfNBootstrap` for a data-driven choice of m.
+    pub fn m_out_of_n(rng: R, m: usize) -> Subsample<R, impl Fn(usize) -> usize + Clone> {
+        Subsample::with_policy(rng, fixed_size_policy(m)).with_mode(SamplingMode::WithReplacement)
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone, F> Re<Sample<T>> for Subsample<R, F>
 where
     F: Fn(usize) -> usize + Clone,
 {
     type Item = Sample<T>;
 
     fn re(&self, sample: &Sample<T>) -> impl Iterator<Item = Self::Item> {
+        self.re_lend(sample)
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone, F> ReLend<Sample<T>> for Subsample<R, F>
+where
+    F: Fn(usize) -> usize + Clone,
+{
+    type Elem = T;
+    type Strategy = Streamed<SubsampleStrategy<R>>;
+
+    fn strategy(&self, sample: &Sample<T>) -> Self::Strategy {
         let subsample_size = (self.policy)(sample.data.len()).min(sample.data.len());
-        Box::new(SubsampleIter::new(
-            &sample.data,
-            self.rng.clone(),
+        Streamed::new(&self.rng, SubsampleStrategy::new(self.rng.clone(), subsample_size, self.mode))
+    }
+}
+
+impl<T: Copy, R: Rng + SeedableRng + Clone, F> ReAt<Sample<T>> for Subsample<R, F>
+where
+    F: Fn(usize) -> usize + Clone,
+{
+    fn re_at(&self, sample: &Sample<T>, index: usize) -> Self::Item {
+        let subsample_size = (self.policy)(sample.data.len()).min(sample.data.len());
+        let strategy = SubsampleStrategy::new(
+            replicate_rng(&self.rng, index),
             subsample_size,
             self.mode,
-        ))
+        );
+        ReIter::new(strategy, sample)
+            .next()
+            .expect("subsample iterator is unbounded")
     }
 }
 
-pub struct SubsampleIter<'a, T, R: Rng> {
-    data: &'a [T],
+/// Draws subsamples of a fixed size.
+pub struct SubsampleStrategy<R: Rng> {
     rng: R,
-    buffer: Vec<T>,
     subsample_size: usize,
     mode: SamplingMode,
-    // Cache for reservoir sampling state
-    reservoir_idx: usize,
 }
 
-impl<'a, T: Copy, R: Rng> SubsampleIter<'a, T, R> {
-    fn new(data: &'a [T], rng: R, subsample_size: usize, mode: SamplingMode) -> Self {
+impl<R: Rng> SubsampleStrategy<R> {
+    pub fn new(rng: R, subsample_size: usize, mode: SamplingMode) -> Self {
         Self {
-            buffer: Vec::with_capacity(subsample_size),
-            data,
             rng,
             subsample_size,
             mode,
-            reservoir_idx: 0,
         }
     }
 
     #[inline(always)]
-    fn sample_without_replacement(&mut self) -> Sample<T> {
-        let n = self.data.len();
+    fn sample_without_replacement<T: Copy>(&mut self, data: &[T], buffer: &mut Vec<T>) {
+        let n = data.len();
         let k = self.subsample_size;
 
         // Adaptive algorithm selection:
         // - Small k (< 25% of n): Reservoir sampling (O(n) time, O(k) space)
         // - Large k (≥ 25% of n): Partial Fisher-Yates (O(k) time, reuses full buffer)
-        if k == 0 {
-            return Sample::new(Vec::new());
-        }
-
         if k < n / 4 {
             // RESERVOIR SAMPLING (optimal for small k)
-            self.buffer.clear();
-            self.buffer.reserve_exact(k);
+            buffer.clear();
+            buffer.reserve_exact(k);
 
             // Phase 1: Fill reservoir with first k elements
             unsafe {
-                self.buffer.set_len(k);
+                buffer.set_len(k);
                 std::ptr::copy_nonoverlapping(
-                    self.data.as_ptr(),
-                    self.buffer.as_mut_ptr(),
+                    data.as_ptr(),
+                    buffer.as_mut_ptr(),
                     k.min(n),
                 );
             }
@@ -125,30 +150,29 @@ impl<'a, T: Copy, R: Rng> SubsampleIter<'a, T, R> {
                 let j = self.rng.gen_range(0..=i);
                 if j < k {
                     unsafe {
-                        *self.buffer.get_unchecked_mut(j) = *self.data.get_unchecked(i);
+                        *buffer.get_unchecked_mut(j) = *data.get_unchecked(i);
                     }
                 }
                 i += 1;
             }
-
-            Sample::new(std::mem::take(&mut self.buffer))
         } else {
             // PARTIAL FISHER-YATES (optimal for large k)
             // Reuse full buffer to avoid reallocations
-            if self.buffer.capacity() < n {
-                self.buffer.reserve_exact(n);
+            buffer.clear();
+            if buffer.capacity() < n {
+                buffer.reserve_exact(n);
             }
 
             unsafe {
-                self.buffer.set_len(n);
+                buffer.set_len(n);
                 std::ptr::copy_nonoverlapping(
-                    self.data.as_ptr(),
-                    self.buffer.as_mut_ptr(),
+                    data.as_ptr(),
+                    buffer.as_mut_ptr(),
                     n,
                 );
 
                 // Shuffle only first k elements
-                let ptr = self.buffer.as_mut_ptr();
+                let ptr = buffer.as_mut_ptr();
                 for i in (n - k..n).rev() {
                     let j = self.rng.gen_range(0..=i);
                     let tmp = *ptr.add(i);
@@ -156,26 +180,25 @@ impl<'a, T: Copy, R: Rng> SubsampleIter<'a, T, R> {
                     *ptr.add(j) = tmp;
                 }
 
-                // Truncate to k elements (no allocation)
-                self.buffer.set_len(k);
+                // Keep the k shuffled elements (tail) at the front, no allocation
+                std::ptr::copy(ptr.add(n - k), ptr, k);
+                buffer.set_len(k);
             }
-
-            Sample::new(std::mem::take(&mut self.buffer))
         }
     }
 
     #[inline(always)]
-    fn sample_with_replacement(&mut self) -> Sample<T> {
-        let n = self.data.len();
+    fn sample_with_replacement<T: Copy>(&mut self, data: &[T], buffer: &mut Vec<T>) {
+        let n = data.len();
         let k = self.subsample_size;
 
-        self.buffer.clear();
-        self.buffer.reserve_exact(k);
+        buffer.clear();
+        buffer.reserve_exact(k);
 
         unsafe {
-            self.buffer.set_len(k);
-            let out_ptr = self.buffer.as_mut_ptr();
-            let data_ptr = self.data.as_ptr();
+            buffer.set_len(k);
+            let out_ptr = buffer.as_mut_ptr();
+            let data_ptr = data.as_ptr();
 
             // Unrolled loop for better ILP (Instruction-Level Parallelism)
             let mut i = 0;
@@ -199,23 +222,24 @@ impl<'a, T: Copy, R: Rng> SubsampleIter<'a, T, R> {
                 i += 1;
             }
         }
-
-        Sample::new(std::mem::take(&mut self.buffer))
     }
 }
 
-impl<'a, T: Copy, R: Rng> Iterator for SubsampleIter<'a, T, R> {
-    type Item = Sample<T>;
+impl_reseed!(SubsampleStrategy<R>);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.data.is_empty() || self.subsample_size == 0 {
-            return Some(Sample::new(Vec::new()));
+impl<T: Copy, R: Rng> ReStrategy<Sample<T>, T> for SubsampleStrategy<R> {
+    fn mutate(&mut self, sample: &Sample<T>, buffer: &mut Vec<T>) -> bool {
+        let data = &sample.data;
+        if data.is_empty() || self.subsample_size == 0 {
+            buffer.clear();
+            return true;
         }
 
-        Some(match self.mode {
-            SamplingMode::WithoutReplacement => self.sample_without_replacement(),
-            SamplingMode::WithReplacement => self.sample_with_replacement(),
-        })
+        match self.mode {
+            SamplingMode::WithoutReplacement => self.sample_without_replacement(data, buffer),
+            SamplingMode::WithReplacement => self.sample_with_replacement(data, buffer),
+        }
+        true
     }
 }
 
@@ -228,11 +252,11 @@ pub fn log_policy(n: usize) -> usize {
     (n as f64).ln().max(1.0) as usize
 }
 
-pub fn fixed_ratio_policy(ratio: f64) -> impl Fn(usize) -> usize {
+pub fn fixed_ratio_policy(ratio: f64) -> impl Fn(usize) -> usize + Clone {
     move |n| ((n as f64) * ratio).max(1.0) as usize
 }
 
-pub fn fixed_size_policy(size: usize) -> impl Fn(usize) -> usize {
+pub fn fixed_size_policy(size: usize) -> impl Fn(usize) -> usize + Clone {
     move |_| size
 }
 
@@ -1,4 +1,9 @@
 mod read;
+mod weighted;
+mod paired;
+
+pub use weighted::Weighted;
+pub use paired::Paired;
 
 use std::iter::Iterator;
 use crate::statistics::Statistic;
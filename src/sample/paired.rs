@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+/// Paired observations `(xᵢ, yᵢ)`: a regressor and its response.
+///
+/// The regressor is shared (`Arc`), so resamplers that keep the design fixed
+/// (residual bootstrap) hand out one response vector per replicate instead of
+/// copying `x`.
+#[derive(Debug, Clone)]
+pub struct Paired<T> {
+    pub x: Arc<[T]>,
+    pub y: Vec<T>,
+}
+
+impl<T> Paired<T> {
+    pub fn new(x: impl Into<Arc<[T]>>, y: Vec<T>) -> Self {
+        let x = x.into();
+        assert_eq!(x.len(), y.len(), "Regressor and response must match");
+        Self { x, y }
+    }
+
+    /// Number of pairs
+    pub fn len(&self) -> usize {
+        self.y.len()
+    }
+
+    /// Check if there are no pairs
+    pub fn is_empty(&self) -> bool {
+        self.y.is_empty()
+    }
+
+    /// `(x, y)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.x.iter().zip(&self.y)
+    }
+}
+
+impl<T> FromIterator<(T, T)> for Paired<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let (x, y): (Vec<T>, Vec<T>) = iter.into_iter().unzip();
+        Self { x: x.into(), y }
+    }
+}
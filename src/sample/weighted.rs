@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use num_traits::Float;
+
+/// Observations paired with non-negative weights.
+///
+/// The observations are shared (`Arc`), so weighted resamplers hand out one
+/// weight vector per replicate instead of copying the data. Weights need not be
+/// normalized: multinomial counts and Dirichlet probabilities describe the same
+/// distribution up to scale.
+#[derive(Debug, Clone)]
+pub struct Weighted<T> {
+    pub data: Arc<[T]>,
+    pub weights: Vec<T>,
+}
+
+impl<T> Weighted<T> {
+    pub fn new(data: impl Into<Arc<[T]>>, weights: Vec<T>) -> Self {
+        let data = data.into();
+        assert_eq!(data.len(), weights.len(), "Data and weights must match");
+        Self { data, weights }
+    }
+
+    /// Number of observations (including zero-weight ones)
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Check if there are no observations
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// `(observation, weight)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.data.iter().zip(&self.weights)
+    }
+}
+
+impl<T: Float> Weighted<T> {
+    /// Equal unit weights: every weighted statistic reproduces its unweighted value.
+    pub fn uniform(data: impl Into<Arc<[T]>>) -> Self {
+        let data = data.into();
+        let weights = vec![T::one(); data.len()];
+        Self { data, weights }
+    }
+
+    /// Total weight `Σwᵢ`
+    pub fn total(&self) -> T {
+        self.weights.iter().fold(T::zero(), |acc, &w| acc + w)
+    }
+
+    /// Weighted central moments `(m₂, m₃, m₄)` around `mean`, normalized by `Σwᵢ`.
+    pub(crate) fn central_moments(&self, mean: T) -> (T, T, T) {
+        let mut sum2 = T::zero();
+        let mut sum3 = T::zero();
+        let mut sum4 = T::zero();
+        for (&x, &w) in self.iter() {
+            let dev = x - mean;
+            let dev2 = dev * dev;
+            sum2 = sum2 + w * dev2;
+            sum3 = sum3 + w * dev2 * dev;
+            sum4 = sum4 + w * dev2 * dev2;
+        }
+        let total = self.total();
+        (sum2 / total, sum3 / total, sum4 / total)
+    }
+}
@@ -0,0 +1,143 @@
+use num_traits::{Float, FromPrimitive};
+use crate::{Mean, Variance};
+
+/// Running, mergeable state of a statistic over weighted observations.
+///
+/// Accumulators see each observation once, so statistics built on them can be
+/// evaluated over streams that never fit in memory, and partial states of
+/// separate chunks can be combined with [`merge`](Self::merge).
+pub trait Accumulator<T> {
+    /// Add observation `x` with weight `w ≥ 0`.
+    fn push(&mut self, x: T, w: T);
+
+    /// Combine with the state of another chunk.
+    fn merge(&mut self, other: &Self);
+
+    /// Current value of the statistic.
+    fn value(&self) -> T;
+}
+
+/// Statistic that can be evaluated through an [`Accumulator`].
+pub trait Mergeable<T> {
+    type Accumulator: Accumulator<T> + Clone;
+
+    /// Empty state.
+    fn accumulator(&self) -> Self::Accumulator;
+}
+
+/// Running weighted mean `Σwᵢxᵢ / Σwᵢ` (West, 1979).
+#[derive(Debug, Clone, Copy)]
+pub struct MeanAccumulator<T> {
+    weight: T,
+    mean: T,
+}
+
+impl<T: Float> Default for MeanAccumulator<T> {
+    fn default() -> Self {
+        Self {
+            weight: T::zero(),
+            mean: T::zero(),
+        }
+    }
+}
+
+impl<T: Float> Accumulator<T> for MeanAccumulator<T> {
+    #[inline]
+    fn push(&mut self, x: T, w: T) {
+        if w <= T::zero() {
+            return;
+        }
+        self.weight = self.weight + w;
+        self.mean = self.mean + (x - self.mean) * (w / self.weight);
+    }
+
+    fn merge(&mut self, other: &Self) {
+        let weight = self.weight + other.weight;
+        if weight <= T::zero() {
+            return;
+        }
+        self.mean = self.mean + (other.mean - self.mean) * (other.weight / weight);
+        self.weight = weight;
+    }
+
+    fn value(&self) -> T {
+        if self.weight > T::zero() { self.mean } else { T::nan() }
+    }
+}
+
+impl<T: Float> Mergeable<T> for Mean {
+    type Accumulator = MeanAccumulator<T>;
+
+    fn accumulator(&self) -> Self::Accumulator {
+        MeanAccumulator::default()
+    }
+}
+
+/// Running weighted variance (West, 1979; Chan et al., 1979 for merging).
+///
+/// Same frequency-weight semantics as `Variance` on `Weighted`: weights are
+/// rescaled to sum to the number of observations `n`, then `ddof` applies.
+#[derive(Debug, Clone, Copy)]
+pub struct VarianceAccumulator<T> {
+    ddof: usize,
+    count: usize,
+    weight: T,
+    mean: T,
+    /// Weighted sum of squared deviations `Σwᵢ(xᵢ - x̄)²`
+    m2: T,
+}
+
+impl<T: Float> VarianceAccumulator<T> {
+    pub fn new(ddof: usize) -> Self {
+        Self {
+            ddof,
+            count: 0,
+            weight: T::zero(),
+            mean: T::zero(),
+            m2: T::zero(),
+        }
+    }
+}
+
+impl<T: Float + FromPrimitive> Accumulator<T> for VarianceAccumulator<T> {
+    #[inline]
+    fn push(&mut self, x: T, w: T) {
+        self.count += 1;
+        if w <= T::zero() {
+            return;
+        }
+        self.weight = self.weight + w;
+        let delta = x - self.mean;
+        self.mean = self.mean + delta * (w / self.weight);
+        self.m2 = self.m2 + w * delta * (x - self.mean);
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        let weight = self.weight + other.weight;
+        if weight <= T::zero() {
+            return;
+        }
+        let delta = other.mean - self.mean;
+        self.m2 = self.m2 + other.m2 + delta * delta * self.weight * other.weight / weight;
+        self.mean = self.mean + delta * (other.weight / weight);
+        self.weight = weight;
+    }
+
+    fn value(&self) -> T {
+        if self.count < 2 || self.count <= self.ddof || self.weight <= T::zero() {
+            return T::nan();
+        }
+        let n = T::from_usize(self.count).expect("usize fits in float");
+        let dof = T::from_usize(self.count - self.ddof).expect("usize fits in float");
+        self.m2 / self.weight * n / dof
+    }
+}
+
+impl<T: Float + FromPrimitive> Mergeable<T> for Variance {
+    type Accumulator = VarianceAccumulator<T>;
+
+    fn accumulator(&self) -> Self::Accumulator {
+        VarianceAccumulator::new(self.ddof)
+    }
+}
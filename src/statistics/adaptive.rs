@@ -0,0 +1,346 @@
+use num_traits::{Float, FromPrimitive};
+use crate::{CDF, Quantile, Re};
+
+use super::Statistic;
+
+/// Quantity whose Monte Carlo error [`Adaptive`] controls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdaptiveTarget {
+    /// Two-sided p-value `(#{|θ*| ≥ |θ̂|} + 1) / (B + 1)` of a centered
+    /// statistic under the null replicates (sign flips, permutations)
+    PValue,
+    /// Standard error of the statistic: the spread of the replicates
+    Se,
+    /// `p`-quantile of the replicates, e.g. a percentile CI endpoint
+    Quantile(f64),
+}
+
+/// Why [`Adaptive`] stopped drawing replicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdaptiveStop {
+    /// Monte Carlo SE fell below the tolerance
+    Converged,
+    /// Besag–Clifford rule: enough exceedances to call the p-value large
+    EarlyStop,
+    /// Replicate budget used up, or the resampler ran out
+    Exhausted,
+}
+
+/// Result of an adaptive Monte Carlo run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveResult<F> {
+    /// Statistic on the original data
+    pub observed_statistic: F,
+    /// The target: p-value, SE or quantile
+    pub estimate: F,
+    /// Monte Carlo standard error of `estimate`
+    pub mc_se: F,
+    /// Replicates drawn
+    pub replicates: usize,
+    pub stop: AdaptiveStop,
+}
+
+/// Draws replicates until the Monte Carlo error of the target is small enough.
+///
+/// `SE::new` and `MeanTest::from_absolute_accuracy` fix `B` up front, sized for
+/// the worst case. This driver checks the Monte Carlo SE of the target after
+/// every `batch` replicates and stops once it is below `tolerance`:
+/// - p-value: binomial `√(p(1-p)/B)`;
+/// - SE: delta method `√((m₄ - m₂²)/B) / 2s` with replicate central moments `mₖ`,
+///   both scaled by the resampler's `variance_scale` as in `SE`;
+/// - quantile: half the distance between the order statistics at `p ± √(p(1-p)/B)`.
+///
+/// The p-value and SE checks cost O(1) from running counts and central
+/// moments. A quantile check sorts the replicates, so after a check at `B`
+/// the next one waits until at least `1.25·B` replicates are drawn.
+///
+/// With [`with_early_stop`](Self::with_early_stop) a p-value run also ends as
+/// soon as `h` replicates are at least as extreme as the observed statistic
+/// (Besag & Clifford, 1991) and reports `h / B`: clearly non-significant tests
+/// then cost about `h / p` replicates instead of the full budget.
+///
+/// # Example
+/// ```rust
+/// use rand::SeedableRng;
+/// use rand_xoshiro::Xoshiro256PlusPlus;
+/// use zima::{Adaptive, AdaptiveResult, Bootstrap, Mean, Sample, Statistic};
+///
+/// let data = Sample::new(vec![2.1, 3.4, 1.9, 4.2, 2.8, 3.3, 2.5, 3.9]);
+/// let se = Adaptive::se(Mean, Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(1)), 0.005);
+/// let result: AdaptiveResult<f64> = se.compute(&data);
+/// println!("SE {:.3} ± {:.4} after {} replicates", result.estimate, result.mc_se, result.replicates);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Adaptive<Stat, R> {
+    pub statistic: Stat,
+    pub resampler: R,
+    pub target: AdaptiveTarget,
+    pub tolerance: f64,
+    pub batch: usize,
+    pub min_replicates: usize,
+    pub max_replicates: usize,
+    /// Besag–Clifford exceedance count `h` (p-values only)
+    pub early_stop: Option<usize>,
+}
+
+impl<Stat, R> Adaptive<Stat, R> {
+    /// Checks every 100 replicates, from 100 up to 1 000 000.
+    ///
+    /// # Panics
+    /// Panics if `tolerance` is not positive.
+    pub fn new(statistic: Stat, resampler: R, target: AdaptiveTarget, tolerance: f64) -> Self {
+        assert!(tolerance > 0.0, "tolerance must be positive");
+        if let AdaptiveTarget::Quantile(p) = target {
+            debug_assert!((0.0..=1.0).contains(&p), "Quantile p must be in [0,1]");
+        }
+        Self {
+            statistic,
+            resampler,
+            target,
+            tolerance,
+            batch: 100,
+            min_replicates: 100,
+            max_replicates: 1_000_000,
+            early_stop: None,
+        }
+    }
+
+    /// Permutation p-value to within `tolerance`.
+    pub fn p_value(statistic: Stat, resampler: R, tolerance: f64) -> Self {
+        Self::new(statistic, resampler, AdaptiveTarget::PValue, tolerance)
+    }
+
+    /// Standard error to within `tolerance`.
+    pub fn se(statistic: Stat, resampler: R, tolerance: f64) -> Self {
+        Self::new(statistic, resampler, AdaptiveTarget::Se, tolerance)
+    }
+
+    /// `p`-quantile of the replicates to within `tolerance`.
+    pub fn quantile(statistic: Stat, resampler: R, p: f64, tolerance: f64) -> Self {
+        Self::new(statistic, resampler, AdaptiveTarget::Quantile(p), tolerance)
+    }
+
+    /// Replicates drawn between two checks of the Monte Carlo error
+    pub fn with_batch(mut self, batch: usize) -> Self {
+        self.batch = batch.max(1);
+        self
+    }
+
+    /// Replicates always drawn before the first check
+    pub fn with_min_replicates(mut self, min_replicates: usize) -> Self {
+        self.min_replicates = min_replicates;
+        self
+    }
+
+    /// Replicate budget
+    pub fn with_max_replicates(mut self, max_replicates: usize) -> Self {
+        self.max_replicates = max_replicates;
+        self
+    }
+
+    /// Besag–Clifford stop after `h` exceedances (typically 10–20).
+    pub fn with_early_stop(mut self, h: usize) -> Self {
+        assert!(h > 0, "h must be positive");
+        self.early_stop = Some(h);
+        self
+    }
+}
+
+/// Running summary of the replicates drawn so far.
+#[derive(Debug, Clone, Default)]
+struct Tally {
+    drawn: usize,
+    /// Replicates at least as extreme as the observed statistic
+    extreme: usize,
+    /// Replicates summarized by the moments
+    count: f64,
+    mean: f64,
+    /// Central moment sums `Σ(x - x̄)ᵏ` for `k = 2, 3, 4`
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    /// Replicates kept for the quantile target
+    values: Vec<f64>,
+}
+
+impl Tally {
+    /// Add `x` to the running moments in O(1) (Pébay, 2008).
+    fn push_moments(&mut self, x: f64) {
+        let previous = self.count;
+        self.count += 1.0;
+        let n = self.count;
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * previous;
+
+        self.mean += delta_n;
+        self.m4 += term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2 - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+    }
+}
+
+impl<D, F, Stat, R> Statistic<D, AdaptiveResult<F>> for Adaptive<Stat, R>
+where
+    F: Float + FromPrimitive,
+    Stat: Statistic<D, F>,
+    R: Re<D, Item = D>,
+{
+    fn compute(&self, data: &D) -> AdaptiveResult<F> {
+        let observed_statistic = self.statistic.compute(data);
+        let observed_abs = observed_statistic.abs();
+
+        let mut tally = Tally::default();
+        let mut next_check = self.min_replicates;
+        let mut stop = AdaptiveStop::Exhausted;
+
+        for resample in self.resampler.re(data).take(self.max_replicates) {
+            let value = self.statistic.compute(&resample);
+            tally.drawn += 1;
+
+            match self.target {
+                AdaptiveTarget::PValue => {
+                    tally.extreme += usize::from(value.abs() >= observed_abs);
+                    if self.early_stop.is_some_and(|h| tally.extreme >= h) {
+                        stop = AdaptiveStop::EarlyStop;
+                        break;
+                    }
+                }
+                AdaptiveTarget::Se => tally.push_moments(value.to_f64().unwrap_or(f64::NAN)),
+                AdaptiveTarget::Quantile(_) => tally.values.extend(value.to_f64()),
+            }
+
+            let drawn = tally.drawn;
+            if drawn >= next_check && drawn % self.batch == 0 {
+                let (_, mc_se) = self.estimate(data, &tally);
+                if mc_se < self.tolerance {
+                    stop = AdaptiveStop::Converged;
+                    break;
+                }
+                if matches!(self.target, AdaptiveTarget::Quantile(_)) {
+                    next_check = drawn + drawn / 4;
+                }
+            }
+        }
+
+        let drawn = tally.drawn as f64;
+        let (estimate, mc_se) = if stop == AdaptiveStop::EarlyStop {
+            let p = tally.extreme as f64 / drawn;
+            (p, (p * (1.0 - p) / drawn).sqrt())
+        } else {
+            self.estimate(data, &tally)
+        };
+        let float = |x: f64| F::from_f64(x).expect("f64 fits in float");
+
+        AdaptiveResult {
+            observed_statistic,
+            estimate: float(estimate),
+            mc_se: float(mc_se),
+            replicates: tally.drawn,
+            stop,
+        }
+    }
+}
+
+impl<Stat, R> Adaptive<Stat, R> {
+    /// Target and its Monte Carlo SE from the replicates tallied so far; NaN when undefined.
+    fn estimate<D>(&self, data: &D, tally: &Tally) -> (f64, f64)
+    where
+        R: Re<D>,
+    {
+        let b = tally.drawn as f64;
+        match self.target {
+            AdaptiveTarget::PValue => {
+                let p = (tally.extreme + 1) as f64 / (b + 1.0);
+                (p, (p * (1.0 - p) / b).sqrt())
+            }
+            AdaptiveTarget::Se => {
+                // Scaled like `SE`: `B/(B-1)` for Monte Carlo resamplers, `n-1` for the jackknife
+                let inflation = tally.count * self.resampler.variance_scale(data, tally.count as usize);
+                let (m2, m4) = (tally.m2 / tally.count, tally.m4 / tally.count);
+                let se = (inflation * m2).sqrt();
+                (se, inflation * ((m4 - m2 * m2).max(0.0) / tally.count).sqrt() / (2.0 * se))
+            }
+            AdaptiveTarget::Quantile(p) => {
+                if tally.values.is_empty() {
+                    return (f64::NAN, f64::NAN);
+                }
+                let ecdf = CDF.compute(&tally.values);
+                let at = |q: f64| Quantile::new(q.clamp(0.0, 1.0)).compute(&ecdf);
+                let spread = (p * (1.0 - p) / b).sqrt();
+                (at(p), (at(p + spread) - at(p - spread)) / 2.0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Flipper, Jackknife, Mean, SE, Sample};
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn stops_when_the_target_is_precise_enough() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(25);
+        let noise: Sample<f64> = (0..40).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let shifted: Sample<f64> = noise.data.iter().map(|x| x + 0.4).collect();
+        let flipper = || Flipper::sign(Xoshiro256PlusPlus::seed_from_u64(1));
+
+        // Null data: Besag–Clifford stops after a few dozen sign flips
+        let null: AdaptiveResult<f64> = Adaptive::p_value(Mean, flipper(), 0.005).with_early_stop(10).compute(&noise);
+        assert_eq!(null.stop, AdaptiveStop::EarlyStop);
+        assert!(null.replicates < 200 && null.estimate > 0.05, "{null:?}");
+
+        // A real shift: the small p-value is resolved to the tolerance
+        let shift: AdaptiveResult<f64> = Adaptive::p_value(Mean, flipper(), 0.002).with_early_stop(10).compute(&shifted);
+        assert_eq!(shift.stop, AdaptiveStop::Converged);
+        assert!(shift.estimate < 0.01 && shift.mc_se < 0.002, "{shift:?}");
+
+        // Bootstrap SE of the mean, σ/√n ≈ 0.577/√40 ≈ 0.091
+        let bootstrap = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(2));
+        let se: AdaptiveResult<f64> = Adaptive::se(Mean, bootstrap.clone(), 0.002).compute(&noise);
+        assert_eq!(se.stop, AdaptiveStop::Converged);
+        assert!((se.estimate / 0.091 - 1.0).abs() < 0.15 && se.replicates > 100, "{se:?}");
+
+        let upper: AdaptiveResult<f64> = Adaptive::quantile(Mean, bootstrap, 0.975, 0.005)
+            .with_max_replicates(500)
+            .compute(&noise);
+        assert_eq!((upper.stop, upper.replicates), (AdaptiveStop::Exhausted, 500));
+        assert!(upper.estimate > se.observed_statistic);
+    }
+
+    #[test]
+    fn se_target_matches_fixed_replicate_se() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(27);
+        let data: Sample<f64> = (0..30).map(|_| rng.gen_range(0.0..3.0)).collect();
+
+        // The jackknife runs out after n replicates and needs its own inflation
+        let adaptive: AdaptiveResult<f64> = Adaptive::se(Mean, Jackknife, 1e-9).compute(&data);
+        let fixed: f64 = SE::jackknife(Mean).compute(&data);
+        assert_eq!((adaptive.stop, adaptive.replicates), (AdaptiveStop::Exhausted, 30));
+        assert!((adaptive.estimate - fixed).abs() < 1e-12, "{} vs {fixed}", adaptive.estimate);
+
+        let bootstrap = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(3));
+        let adaptive: AdaptiveResult<f64> = Adaptive::se(Mean, bootstrap.clone(), 0.005).compute(&data);
+        let fixed: f64 = SE::new(Mean, bootstrap, adaptive.replicates).compute(&data);
+        assert!((adaptive.estimate - fixed).abs() < 1e-12, "{} vs {fixed}", adaptive.estimate);
+    }
+
+    #[test]
+    fn running_moments_match_two_pass_ones() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(26);
+        let values: Vec<f64> = (0..500).map(|_| rng.gen_range(0.0..1.0_f64).powi(3) + 100.0).collect();
+        let mut tally = Tally::default();
+        values.iter().for_each(|&x| tally.push_moments(x));
+
+        let mean = values.iter().sum::<f64>() / 500.0;
+        let moment = |k: i32| values.iter().map(|x| (x - mean).powi(k)).sum::<f64>();
+        assert!((tally.mean - mean).abs() < 1e-12);
+        for (running, direct) in [(tally.m2, moment(2)), (tally.m3, moment(3)), (tally.m4, moment(4))] {
+            assert!((running / direct - 1.0).abs() < 1e-9, "{running} vs {direct}");
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use num_traits::{Float, FromPrimitive};
 use crate::statistics::*;
+use crate::Weighted;
 
 /// Sample excess kurtosis (fourth standardized cumulant).
 ///
@@ -75,6 +76,13 @@ where
         let m2 = sum2 / n_f;
         let m4 = sum4 / n_f;
 
+        self.from_moments(n_f, m2, m4)
+    }
+}
+
+impl Kurtosis {
+    /// Excess kurtosis from the biased central moments `m2`, `m4` of `n` observations.
+    fn from_moments<T: Float + FromPrimitive>(&self, n_f: T, m2: T, m4: T) -> T {
         if self.unbiased {
             // Unbiased κ̂₂ = n/(n-1) * m2
             // Unbiased κ̂₄ = [n²(n+1)m4 - 3n(n-1)m2²] / [(n-1)(n-2)(n-3)]
@@ -105,3 +113,24 @@ where
         }
     }
 }
+
+/// Weighted excess kurtosis: biased moments use the normalized weights, the
+/// small-sample correction uses the number of observations.
+impl<T> Statistic<Weighted<T>, T> for Kurtosis
+where
+    T: Float + FromPrimitive,
+{
+    fn compute(&self, data: &Weighted<T>) -> T {
+        let n = data.len();
+        if n < 4 && self.unbiased {
+            return T::nan();
+        }
+        if n < 2 {
+            return T::nan();
+        }
+
+        let mean = Mean.compute(data);
+        let (m2, _, m4) = data.central_moments(mean);
+        self.from_moments(T::from_usize(n).expect("n fits in float"), m2, m4)
+    }
+}
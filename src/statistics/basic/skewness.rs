@@ -1,5 +1,6 @@
 use num_traits::{Float, FromPrimitive};
 use crate::statistics::*;
+use crate::Weighted;
 
 /// Sample skewness (third standardized moment).
 ///
@@ -75,6 +76,13 @@ where
         let m2 = sum2 / n_f; // Biased variance (κ₂ when ddof=0)
         let m3 = sum3 / n_f; // Biased third central moment
 
+        self.from_moments(n_f, m2, m3)
+    }
+}
+
+impl Skewness {
+    /// Skewness from the biased central moments `m2`, `m3` of `n` observations.
+    fn from_moments<T: Float + FromPrimitive>(&self, n_f: T, m2: T, m3: T) -> T {
         if self.unbiased {
             // Unbiased κ̂₂ = n/(n-1) * m2   [same as Variance{ddof=1}]
             // Unbiased κ̂₃ = [n² / ((n-1)(n-2))] * m3
@@ -105,3 +113,24 @@ where
         }
     }
 }
+
+/// Weighted skewness: biased moments use the normalized weights, the small-sample
+/// correction uses the number of observations.
+impl<T> Statistic<Weighted<T>, T> for Skewness
+where
+    T: Float + FromPrimitive,
+{
+    fn compute(&self, data: &Weighted<T>) -> T {
+        let n = data.len();
+        if n < 3 && self.unbiased {
+            return T::nan();
+        }
+        if n < 2 {
+            return T::nan();
+        }
+
+        let mean = Mean.compute(data);
+        let (m2, m3, _) = data.central_moments(mean);
+        self.from_moments(T::from_usize(n).expect("n fits in float"), m2, m3)
+    }
+}
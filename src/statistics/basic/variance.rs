@@ -1,5 +1,6 @@
 use num_traits::{Float, FromPrimitive};
 use crate::statistics::*;
+use crate::Weighted;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Variance {
@@ -53,3 +54,25 @@ where
         sq_sum / dof
     }
 }
+
+/// Weighted variance with frequency-weight semantics: weights are rescaled to
+/// sum to the number of observations `n`, so multinomial counts reproduce the
+/// variance of the copied bootstrap sample exactly.
+impl<T> Statistic<Weighted<T>, T> for Variance
+where
+    T: Float + FromPrimitive,
+{
+    fn compute(&self, data: &Weighted<T>) -> T {
+        let n = data.len();
+        if n < 2 {
+            return T::nan();
+        }
+
+        let mean = Mean.compute(data);
+        let (m2, _, _) = data.central_moments(mean);
+
+        let n_f = T::from_usize(n).expect("usize fits in float");
+        let dof = T::from_usize(n - self.ddof).expect("usize fits in float");
+        m2 * n_f / dof
+    }
+}
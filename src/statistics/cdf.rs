@@ -1,4 +1,5 @@
 use super::Statistic;
+use crate::Weighted;
 use num_traits::Float;
 use std::cmp::Ordering;
 use std::fmt::Debug;
@@ -237,6 +238,107 @@ where
     }
 }
 
+/// Weighted ECDF: Fₙ(x) = Σᵢ wᵢ·I(Xᵢ ≤ x) / Σᵢ wᵢ
+///
+/// Follows the [`EmpiricalCDF`] float policy: NaN observations are dropped,
+/// and so are observations with zero (or NaN) weight.
+#[derive(Debug, Clone)]
+pub struct WeightedEmpiricalCDF<T> {
+    sorted: Vec<T>,
+    /// Running sums of the weights of `sorted`
+    cumulative: Vec<T>,
+}
+
+impl<T: Float> WeightedEmpiricalCDF<T> {
+    /// Creates a weighted ECDF from observation/weight pairs.
+    pub fn from_weighted(data: &Weighted<T>) -> Self {
+        let mut pairs: Vec<(T, T)> = data
+            .iter()
+            .map(|(&x, &w)| (x, w))
+            .filter(|&(x, w)| !x.is_nan() && w > T::zero())
+            .collect();
+        pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("NaNs already filtered"));
+
+        let mut total = T::zero();
+        let (sorted, cumulative) = pairs
+            .into_iter()
+            .map(|(x, w)| {
+                total = total + w;
+                (x, total)
+            })
+            .unzip();
+
+        Self { sorted, cumulative }
+    }
+
+    /// Number of support points (observations with positive weight)
+    #[inline]
+    pub fn n(&self) -> usize {
+        self.sorted.len()
+    }
+
+    #[inline]
+    pub fn points(&self) -> &[T] {
+        &self.sorted
+    }
+
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+
+    /// Total weight `Σwᵢ`
+    #[inline]
+    pub fn total(&self) -> T {
+        self.cumulative.last().copied().unwrap_or_else(T::zero)
+    }
+
+    /// Evaluates the weighted ECDF; same special cases as [`EmpiricalCDF::eval_float`].
+    pub fn eval_float(&self, x: &T) -> f64 {
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        if x.is_infinite() {
+            return if x.is_sign_positive() { 1.0 } else { 0.0 };
+        }
+        if self.sorted.is_empty() {
+            return f64::NAN;
+        }
+
+        let idx = self.sorted.partition_point(|v| v <= x);
+        let below = match idx.checked_sub(1) {
+            Some(last) => self.cumulative.get(last).copied().unwrap_or_else(T::zero),
+            None => T::zero(),
+        };
+        (below / self.total()).to_f64().unwrap_or(f64::NAN)
+    }
+
+    /// Smallest support point `x` with Fₙ(x) ≥ p.
+    pub(crate) fn inverse(&self, p: f64) -> T {
+        assert!(!self.sorted.is_empty(), "Quantile undefined for empty distribution");
+        let target = T::from(p).expect("probability fits in float") * self.total();
+        let idx = self
+            .cumulative
+            .partition_point(|&c| c < target)
+            .min(self.sorted.len() - 1);
+        self.sorted.get(idx).copied().expect("index clamped to support")
+    }
+}
+
+// Implementation for weighted samples
+impl<T> Statistic<Weighted<T>, WeightedEmpiricalCDF<T>> for CDF
+where
+    T: Float,
+{
+    #[inline]
+    fn compute(&self, data: &Weighted<T>) -> WeightedEmpiricalCDF<T> {
+        WeightedEmpiricalCDF::from_weighted(data)
+    }
+}
+
 // use plotters::coord::Shift;
 // use plotters::prelude::*;
 // use statrs::distribution::{Normal, ContinuousCDF};
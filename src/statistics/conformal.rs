@@ -0,0 +1,224 @@
+use num_traits::{Float, FromPrimitive};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::{CDF, Indexed, Interval, Jackknife, Paired, Quantile, Re, Split};
+
+use super::Statistic;
+
+/// Finite-sample conformal quantile: the `⌈(m+1)·c⌉`-th smallest of the `m`
+/// scores, `+∞` when that rank exceeds `m`.
+///
+/// Appending `+∞` turns the type-1 `c`-quantile of the `m+1` points into
+/// exactly this rank.
+fn conformal_quantile<T: Float>(scores: &[T], confidence: f64) -> T {
+    let mut augmented = scores.to_vec();
+    augmented.push(T::infinity());
+    Quantile::new(confidence).compute(&CDF.compute(&augmented))
+}
+
+/// Split conformal prediction (Papadopoulos et al., 2002; Lei et al., 2018).
+///
+/// A random part of the pairs fits the point predictor, the rest calibrates the
+/// absolute residuals. For exchangeable data the band `ŷ(x) ± q̂` covers a new
+/// response with probability at least `confidence`, whatever the predictor or
+/// error distribution. `fit` maps training pairs to a predictor `x ↦ ŷ`.
+#[derive(Debug, Clone)]
+pub struct SplitConformal<F, R> {
+    fit: F,
+    rng: R,
+    calibration: f64,
+    confidence: f64,
+}
+
+impl<F, R: Rng> SplitConformal<F, R> {
+    /// Half of the pairs calibrate the band.
+    pub fn new(fit: F, rng: R, confidence: f64) -> Self {
+        debug_assert!((0.0..1.0).contains(&confidence));
+        Self {
+            fit,
+            rng,
+            calibration: 0.5,
+            confidence,
+        }
+    }
+
+    /// Fraction of the pairs held out for calibration
+    pub fn with_calibration(mut self, fraction: f64) -> Self {
+        assert!(fraction > 0.0 && fraction < 1.0, "Calibration fraction must be in (0, 1)");
+        self.calibration = fraction;
+        self
+    }
+}
+
+/// Prediction band `ŷ(x) ± radius` of a calibrated conformal predictor.
+#[derive(Debug, Clone)]
+pub struct ConformalBand<P, T> {
+    pub predictor: P,
+    /// Conformal quantile of the calibration residuals
+    pub radius: T,
+    pub confidence: f64,
+}
+
+impl<P: Fn(T) -> T, T: Float> ConformalBand<P, T> {
+    /// Prediction interval for the response at `x`.
+    pub fn interval(&self, x: T) -> Interval<T> {
+        let prediction = (self.predictor)(x);
+        Interval::new(prediction - self.radius, prediction + self.radius)
+            .estimate(prediction)
+            .confidence(self.confidence)
+    }
+}
+
+impl<T, F, P, R> Statistic<Paired<T>, ConformalBand<P, T>> for SplitConformal<F, R>
+where
+    T: Float + FromPrimitive,
+    F: Fn(&Paired<T>) -> P,
+    P: Fn(T) -> T,
+    R: Rng + Clone,
+{
+    fn compute(&self, data: &Paired<T>) -> ConformalBand<P, T> {
+        let n = data.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.shuffle(&mut self.rng.clone());
+
+        // At least one pair on each side when there are two
+        let held_out = ((n as f64 * self.calibration).round() as usize).clamp(1, n.max(2) - 1);
+        let (test, train) = order.split_at(held_out.min(n));
+        let (training, calibration) = Split {
+            train: train.to_vec(),
+            test: test.to_vec(),
+        }
+        .apply(data);
+
+        let predictor = (self.fit)(&training);
+        let residuals: Vec<T> = calibration.iter().map(|(&x, &y)| (y - predictor(x)).abs()).collect();
+
+        ConformalBand {
+            radius: conformal_quantile(&residuals, self.confidence),
+            predictor,
+            confidence: self.confidence,
+        }
+    }
+}
+
+/// Jackknife+ prediction intervals (Barber, Candès, Ramdas & Tibshirani, 2021).
+///
+/// Uses every pair both to fit and to calibrate: the predictor is refitted on
+/// each leave-one-out sample of [`Jackknife`], and the interval at `x` is read
+/// off the leave-one-out predictions shifted by their own residuals,
+/// `μ̂₋ᵢ(x) ± |yᵢ - μ̂₋ᵢ(xᵢ)|`. Coverage is at least `1 - 2α` in finite samples
+/// and close to `1 - α` in practice, at the cost of `n` fits.
+#[derive(Debug, Clone)]
+pub struct JackknifePlus<F> {
+    fit: F,
+    confidence: f64,
+}
+
+impl<F> JackknifePlus<F> {
+    pub fn new(fit: F, confidence: f64) -> Self {
+        debug_assert!((0.0..1.0).contains(&confidence));
+        Self { fit, confidence }
+    }
+}
+
+/// Leave-one-out predictors and residuals of a [`JackknifePlus`] fit.
+#[derive(Debug, Clone)]
+pub struct JackknifePlusBand<P, T> {
+    /// Predictor fitted on all pairs (the interval estimate)
+    pub predictor: P,
+    /// Predictors `μ̂₋ᵢ` without pair `i`
+    pub leave_one_out: Vec<P>,
+    /// Leave-one-out residuals `|yᵢ - μ̂₋ᵢ(xᵢ)|`
+    pub residuals: Vec<T>,
+    pub confidence: f64,
+}
+
+impl<P: Fn(T) -> T, T: Float> JackknifePlusBand<P, T> {
+    /// Prediction interval for the response at `x`.
+    pub fn interval(&self, x: T) -> Interval<T> {
+        let (lower_scores, upper_scores): (Vec<T>, Vec<T>) = self
+            .leave_one_out
+            .iter()
+            .zip(&self.residuals)
+            .map(|(predictor, &residual)| {
+                let prediction = predictor(x);
+                (residual - prediction, prediction + residual)
+            })
+            .unzip();
+
+        let lower = -conformal_quantile(&lower_scores, self.confidence);
+        let upper = conformal_quantile(&upper_scores, self.confidence);
+        Interval::new(lower, upper)
+            .estimate((self.predictor)(x))
+            .confidence(self.confidence)
+    }
+}
+
+impl<T, F, P> Statistic<Paired<T>, JackknifePlusBand<P, T>> for JackknifePlus<F>
+where
+    T: Float + FromPrimitive,
+    F: Fn(&Paired<T>) -> P,
+    P: Fn(T) -> T,
+{
+    fn compute(&self, data: &Paired<T>) -> JackknifePlusBand<P, T> {
+        let (leave_one_out, residuals): (Vec<P>, Vec<T>) = Indexed::new(Jackknife)
+            .re(data)
+            .zip(data.iter())
+            .map(|(without, (&x, &y))| {
+                let predictor = (self.fit)(&without);
+                let residual = (y - predictor(x)).abs();
+                (predictor, residual)
+            })
+            .unzip();
+
+        JackknifePlusBand {
+            predictor: (self.fit)(data),
+            leave_one_out,
+            residuals,
+            confidence: self.confidence,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LeastSquares;
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    /// `y = 1 + 2x + ε` with uniform errors on `(-1, 1)`
+    fn draw(rng: &mut Xoshiro256PlusPlus, n: usize) -> Paired<f64> {
+        (0..n)
+            .map(|_| {
+                let x = rng.gen_range(0.0..5.0);
+                (x, 1.0 + 2.0 * x + rng.gen_range(-1.0..1.0))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bands_reach_nominal_coverage_of_new_responses() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(23);
+        let data = draw(&mut rng, 80);
+        let fresh = draw(&mut rng, 4000);
+        let fit = |pairs: &Paired<f64>| {
+            let line = LeastSquares.compute(pairs);
+            move |x: f64| line.predict(x)
+        };
+        let coverage = |interval: &dyn Fn(f64) -> Interval<f64>| {
+            fresh.iter().filter(|&(&x, y)| interval(x).contains(y)).count() as f64 / fresh.len() as f64
+        };
+
+        let split: ConformalBand<_, f64> = SplitConformal::new(fit, Xoshiro256PlusPlus::seed_from_u64(1), 0.9).compute(&data);
+        let plus: JackknifePlusBand<_, f64> = JackknifePlus::new(fit, 0.9).compute(&data);
+        assert_eq!(plus.residuals.len(), 80);
+
+        let (split_coverage, plus_coverage) = (coverage(&|x| split.interval(x)), coverage(&|x| plus.interval(x)));
+        assert!((0.85..0.98).contains(&split_coverage), "{split_coverage}");
+        assert!((0.85..0.98).contains(&plus_coverage), "{plus_coverage}");
+
+        // Ideal half-width is the 90% error quantile, 0.9
+        assert!((plus.interval(2.5).width() / 1.8 - 1.0).abs() < 0.2);
+    }
+}
@@ -0,0 +1,265 @@
+use num_traits::{Float, FromPrimitive};
+use crate::{CDF, Interval, QuantileInterval, Re, Stream};
+
+use super::Statistic;
+
+/// Interval construction that [`DoubleBootstrap`] calibrates.
+///
+/// An interval is read off quantiles of replicate *roots*; it covers the true
+/// value exactly when the root [`target`](Self::target) of the truth lies
+/// between those quantiles.
+pub trait Pivot<D, T> {
+    /// Root of a replicate `data` with estimate `theta`, drawn from a parent with estimate `parent`.
+    fn root(&self, data: &D, theta: T, parent: T) -> Option<T>;
+
+    /// Root value the replicates of `data` must bracket for its interval to cover `truth`.
+    fn target(&self, data: &D, theta: T, truth: T) -> Option<T>;
+
+    /// Interval of `data` from the root quantiles `(lower, upper)`.
+    fn invert(&self, data: &D, theta_hat: T, lower: T, upper: T) -> Option<(T, T)>;
+}
+
+/// Percentile interval `[θ*₍λ₁₎, θ*₍λ₂₎]`: the roots are the replicates themselves.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PercentilePivot;
+
+impl<D, T: Float> Pivot<D, T> for PercentilePivot {
+    fn root(&self, _data: &D, theta: T, _parent: T) -> Option<T> {
+        (!theta.is_nan()).then_some(theta)
+    }
+
+    fn target(&self, _data: &D, _theta: T, truth: T) -> Option<T> {
+        Some(truth)
+    }
+
+    fn invert(&self, _data: &D, _theta_hat: T, lower: T, upper: T) -> Option<(T, T)> {
+        Some((lower, upper))
+    }
+}
+
+/// Studentized interval `[θ̂ - t*₍λ₂₎·SE, θ̂ - t*₍λ₁₎·SE]` with `t* = (θ̂* - θ̂) / SE*`.
+///
+/// `se` is evaluated on every replicate of both levels, so it should be cheap
+/// (an analytic formula such as [`SEMean`](crate::SEMean), or the jackknife).
+#[derive(Debug, Clone, Copy)]
+pub struct StudentizedPivot<Se> {
+    pub se: Se,
+}
+
+impl<D, T: Float, Se: Statistic<D, T>> Pivot<D, T> for StudentizedPivot<Se> {
+    fn root(&self, data: &D, theta: T, parent: T) -> Option<T> {
+        let se = self.se.compute(data);
+        if theta.is_nan() || se.is_nan() || se.is_zero() {
+            None
+        } else {
+            Some((theta - parent) / se)
+        }
+    }
+
+    fn target(&self, data: &D, theta: T, truth: T) -> Option<T> {
+        self.root(data, theta, truth)
+    }
+
+    fn invert(&self, data: &D, theta_hat: T, lower: T, upper: T) -> Option<(T, T)> {
+        let se = self.se.compute(data);
+        if se.is_nan() || se.is_zero() {
+            None
+        } else {
+            Some((theta_hat - upper * se, theta_hat - lower * se))
+        }
+    }
+}
+
+/// Iterated (double) bootstrap interval (Hall, 1986; Beran, 1987).
+///
+/// Every outer replicate `X*` is resampled again, the `i`-th one with stream `i`
+/// of the inner resampler (see [`Stream`]), and the inner replicates tell
+/// where the root target of `θ̂` falls in the inner root distribution,
+/// `u = Ĝ**(target)`. The nominal interval of `X*` covers `θ̂` exactly when
+/// `λ₁ ≤ u ≤ λ₂`, so the `α/2` and `1-α/2` quantiles of the `u`'s are the levels
+/// at which the outer interval actually attains the requested coverage. The
+/// outer roots are then read at these calibrated levels.
+///
+/// Costs `samples × inner_samples` statistic evaluations; in exchange the
+/// coverage error drops by an order of `n^{-1/2}`, which matters most for small
+/// samples where percentile intervals under-cover.
+#[derive(Debug, Clone)]
+pub struct DoubleBootstrap<Stat, OuterResampler, InnerResampler, P = PercentilePivot> {
+    statistic: Stat,
+    resampler: OuterResampler,
+    samples: usize,
+    inner: InnerResampler,
+    inner_samples: usize,
+    pivot: P,
+    confidence: f64,
+}
+
+impl<Stat, OuterResampler, InnerResampler> DoubleBootstrap<Stat, OuterResampler, InnerResampler> {
+    /// Calibrated percentile interval from `samples` outer replicates with
+    /// `inner_samples` inner replicates each.
+    pub fn new(
+        statistic: Stat,
+        resampler: OuterResampler,
+        samples: usize,
+        inner: InnerResampler,
+        inner_samples: usize,
+        confidence: f64,
+    ) -> Self {
+        debug_assert!((0.0..1.0).contains(&confidence));
+        Self {
+            statistic,
+            resampler,
+            samples,
+            inner,
+            inner_samples,
+            pivot: PercentilePivot,
+            confidence,
+        }
+    }
+}
+
+impl<Stat, OuterResampler, InnerResampler, P> DoubleBootstrap<Stat, OuterResampler, InnerResampler, P> {
+    /// Calibrate the studentized interval instead, standardizing by `se`.
+    pub fn studentized<Se>(self, se: Se) -> DoubleBootstrap<Stat, OuterResampler, InnerResampler, StudentizedPivot<Se>> {
+        self.with_pivot(StudentizedPivot { se })
+    }
+
+    /// Calibrate an arbitrary interval construction.
+    pub fn with_pivot<Q>(self, pivot: Q) -> DoubleBootstrap<Stat, OuterResampler, InnerResampler, Q> {
+        DoubleBootstrap {
+            statistic: self.statistic,
+            resampler: self.resampler,
+            samples: self.samples,
+            inner: self.inner,
+            inner_samples: self.inner_samples,
+            pivot,
+            confidence: self.confidence,
+        }
+    }
+
+    /// Calibrated quantile levels `(λ₁, λ₂)` and the outer roots they apply to.
+    fn calibrate<D, T>(&self, data: &D, theta_hat: T) -> Option<((f64, f64), Vec<T>)>
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<D, T>,
+        OuterResampler: Re<D, Item = D>,
+        InnerResampler: Re<D, Item = D> + Stream,
+        P: Pivot<D, T>,
+    {
+        let mut roots: Vec<T> = Vec::with_capacity(self.samples);
+        let mut positions: Vec<f64> = Vec::with_capacity(self.samples);
+
+        for (index, resample) in self.resampler.re(data).take(self.samples).enumerate() {
+            let theta_star = self.statistic.compute(&resample);
+            let Some(root) = self.pivot.root(&resample, theta_star, theta_hat) else {
+                continue;
+            };
+            roots.push(root);
+
+            let Some(target) = self.pivot.target(&resample, theta_star, theta_hat) else {
+                continue;
+            };
+            let inner_roots: Vec<T> = self.inner
+                .stream(index)
+                .re(&resample)
+                .take(self.inner_samples)
+                .filter_map(|inner| {
+                    let theta = self.statistic.compute(&inner);
+                    self.pivot.root(&inner, theta, theta_star)
+                })
+                .collect();
+            if !inner_roots.is_empty() {
+                positions.push(CDF.compute(&inner_roots).eval_float(&target));
+            }
+        }
+
+        if roots.len() < 2 || positions.len() < 2 {
+            return None;
+        }
+        let levels = QuantileInterval::percentile(self.confidence).compute(&CDF.compute(&positions));
+        Some((levels, roots))
+    }
+
+    /// Quantile levels `(λ₁, λ₂)` at which the outer interval attains the
+    /// requested coverage (NaN when too few replicates are defined).
+    pub fn calibrated_levels<D, T>(&self, data: &D) -> (f64, f64)
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<D, T>,
+        OuterResampler: Re<D, Item = D>,
+        InnerResampler: Re<D, Item = D> + Stream,
+        P: Pivot<D, T>,
+    {
+        let theta_hat = self.statistic.compute(data);
+        self.calibrate(data, theta_hat)
+            .map_or((f64::NAN, f64::NAN), |(levels, _)| levels)
+    }
+}
+
+impl<D, T, Stat, OuterResampler, InnerResampler, P> Statistic<D, Interval<T>>
+    for DoubleBootstrap<Stat, OuterResampler, InnerResampler, P>
+where
+    T: Float + FromPrimitive,
+    Stat: Statistic<D, T>,
+    OuterResampler: Re<D, Item = D>,
+    InnerResampler: Re<D, Item = D> + Stream,
+    P: Pivot<D, T>,
+{
+    fn compute(&self, data: &D) -> Interval<T> {
+        let theta_hat = self.statistic.compute(data);
+        if theta_hat.is_nan() {
+            return Interval::nan();
+        }
+        let Some(((lambda_lower, lambda_upper), roots)) = self.calibrate(data, theta_hat) else {
+            return Interval::nan();
+        };
+
+        let (root_lower, root_upper) =
+            QuantileInterval::new(lambda_lower, lambda_upper).compute(&CDF.compute(&roots));
+        match self.pivot.invert(data, theta_hat, root_lower, root_upper) {
+            Some((lower, upper)) => Interval::new(lower, upper)
+                .estimate(theta_hat)
+                .confidence(self.confidence),
+            None => Interval::nan(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Mean, SEMean, Sample};
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn calibration_widens_small_sample_percentile_interval() {
+        // Skewed sample of 12 exponential observations with mean 1
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(17);
+        let sample: Sample<f64> = (0..12).map(|_| -(1.0 - rng.r#gen::<f64>()).ln()).collect();
+
+        let double = DoubleBootstrap::new(
+            Mean,
+            Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(1)),
+            500,
+            Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(2)),
+            200,
+            0.9,
+        );
+        let (lambda_lower, lambda_upper) = double.calibrated_levels::<_, f64>(&sample);
+        // The nominal 90% percentile interval under-covers: calibration asks for more
+        assert!(lambda_upper - lambda_lower > 0.9 && lambda_upper > 0.95, "({lambda_lower}, {lambda_upper})");
+
+        let calibrated: Interval<f64> = double.compute(&sample);
+        let roots: Vec<f64> = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(1))
+            .re(&sample)
+            .take(500)
+            .map(|resample| Mean.compute(&resample))
+            .collect();
+        let (lower, upper) = QuantileInterval::percentile(0.9).compute(&CDF.compute(&roots));
+        assert!(calibrated.width() > upper - lower, "{calibrated:?} vs [{lower}, {upper}]");
+
+        let studentized: Interval<f64> = double.studentized(SEMean::default()).compute(&sample);
+        assert!(studentized.contains(&1.0), "{studentized:?}");
+    }
+}
@@ -0,0 +1,166 @@
+use num_traits::{Float, FromPrimitive};
+use crate::{Accumulator, Gather, Indexed, MeanAccumulator, ReLend, Sample, VarianceAccumulator};
+
+use super::Statistic;
+
+/// Jackknife-after-bootstrap (Efron, 1992).
+///
+/// Records the indices drawn by every replicate through [`Indexed`]. Replicates
+/// that happen not to contain observation `i` are replicates of the sample with
+/// `i` deleted, so the jackknife of any bootstrap quantity comes for free: with
+/// `Bootstrap` about `B·e⁻¹ ≈ 0.37·B` replicates are available per observation,
+/// without a second level of resampling. Any index-drawing resampler works
+/// (`Bootstrap`, `Subsample`, …), and the data can be any [`Gather`] columns.
+#[derive(Clone, Copy, Debug)]
+pub struct JackknifeAfterBootstrap<Stat, R> {
+    statistic: Stat,
+    resampler: Indexed<R>,
+    samples: usize,
+}
+
+impl<Stat, R> JackknifeAfterBootstrap<Stat, R> {
+    pub fn new(statistic: Stat, resampler: R, samples: usize) -> Self {
+        Self {
+            statistic,
+            resampler: Indexed::new(resampler),
+            samples,
+        }
+    }
+}
+
+/// Per-observation diagnostics of a [`JackknifeAfterBootstrap`] run.
+#[derive(Debug, Clone)]
+pub struct Influence<T> {
+    /// Full-sample estimate θ̂
+    pub estimate: T,
+    /// Bootstrap standard error from all replicates
+    pub se: T,
+    /// Number of replicates that exclude each observation
+    pub excluded: Vec<usize>,
+    /// Mean `θ̄*₍ᵢ₎` of the replicates excluding observation `i`
+    pub estimates: Vec<T>,
+    /// Standard deviation `SE*₍ᵢ₎` of the replicates excluding observation `i`
+    pub ses: Vec<T>,
+    /// Empirical (jackknife) influence `lᵢ = (n-1)(θ̄*₍·₎ - θ̄*₍ᵢ₎)`
+    pub influence: Vec<T>,
+}
+
+impl<T: Float + FromPrimitive> Influence<T> {
+    /// Jackknife standard error of the bootstrap SE:
+    /// `√((n-1)/n · Σ(SE*₍ᵢ₎ - SE*₍·₎)²)`.
+    pub fn se_of_se(&self) -> T {
+        let n = T::from_usize(self.ses.len()).expect("usize fits in float");
+        let mean = self.ses.iter().fold(T::zero(), |sum, &se| sum + se) / n;
+        let spread = self.ses.iter().fold(T::zero(), |sum, &se| sum + (se - mean).powi(2));
+        ((n - T::one()) / n * spread).sqrt()
+    }
+
+    /// Index of the observation with the largest absolute influence.
+    pub fn most_influential(&self) -> Option<usize> {
+        self.influence
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| !l.is_nan())
+            .max_by(|(_, a), (_, b)| a.abs().partial_cmp(&b.abs()).expect("NaNs already filtered"))
+            .map(|(i, _)| i)
+    }
+}
+
+impl<D, T, Stat, R> Statistic<D, Influence<T>> for JackknifeAfterBootstrap<Stat, R>
+where
+    D: Gather,
+    T: Float + FromPrimitive,
+    Stat: Statistic<D, T>,
+    R: ReLend<Sample<usize>, Elem = usize>,
+{
+    fn compute(&self, data: &D) -> Influence<T> {
+        let n = data.rows();
+        let mut overall = VarianceAccumulator::new(1);
+        let mut means = vec![MeanAccumulator::default(); n];
+        let mut variances = vec![VarianceAccumulator::new(1); n];
+        let mut excluded = vec![0; n];
+        let mut present = vec![false; n];
+
+        for indices in self.resampler.indices(n).take(self.samples) {
+            let theta_star = self.statistic.compute(&data.gather(&indices));
+            if theta_star.is_nan() {
+                continue;
+            }
+            overall.push(theta_star, T::one());
+
+            present.iter_mut().for_each(|seen| *seen = false);
+            for &i in &indices {
+                if let Some(seen) = present.get_mut(i) {
+                    *seen = true;
+                }
+            }
+            for (((seen, count), mean), variance) in
+                present.iter().zip(&mut excluded).zip(&mut means).zip(&mut variances)
+            {
+                if !seen {
+                    *count += 1;
+                    mean.push(theta_star, T::one());
+                    variance.push(theta_star, T::one());
+                }
+            }
+        }
+
+        let estimates: Vec<T> = means.iter().map(Accumulator::value).collect();
+        let ses: Vec<T> = variances.iter().map(|variance| variance.value().sqrt()).collect();
+
+        let n_f = T::from_usize(n).expect("usize fits in float");
+        let center = estimates.iter().fold(T::zero(), |sum, &theta| sum + theta) / n_f;
+        let influence = estimates
+            .iter()
+            .map(|&theta| (n_f - T::one()) * (center - theta))
+            .collect();
+
+        Influence {
+            estimate: self.statistic.compute(data),
+            se: overall.value().sqrt(),
+            excluded,
+            estimates,
+            ses,
+            influence,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bootstrap, Mean, Re};
+    use rand::{Rng, SeedableRng};
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn outlier_dominates_mean_influence() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(8);
+        let mut values: Vec<f64> = (0..20).map(|_| rng.r#gen::<f64>()).collect();
+        if let Some(x) = values.get_mut(7) {
+            *x = 10.0;
+        }
+        let sample = Sample::new(values.clone());
+
+        // Recorded indices reproduce the replicates `re` draws
+        let bootstrap = Bootstrap::new(Xoshiro256PlusPlus::seed_from_u64(3));
+        let draws = Indexed::new(bootstrap.clone()).indices(sample.len());
+        for (indices, replicate) in draws.zip(bootstrap.re(&sample)).take(5) {
+            let rebuilt: Vec<f64> = indices.iter().filter_map(|&i| values.get(i).copied()).collect();
+            assert_eq!(rebuilt, replicate.data);
+        }
+
+        let diagnostics: Influence<f64> = JackknifeAfterBootstrap::new(Mean, bootstrap, 4000).compute(&sample);
+        assert_eq!(diagnostics.most_influential(), Some(7));
+        assert!(diagnostics.excluded.iter().all(|&count| (1200..1700).contains(&count)));
+
+        // Influence of the mean is xᵢ - x̄; without the outlier the mean and SE collapse
+        let mean = values.iter().sum::<f64>() / 20.0;
+        let rest = (values.iter().sum::<f64>() - 10.0) / 19.0;
+        let at = |values: &[f64]| values.get(7).copied().unwrap_or(f64::NAN);
+        let (estimate, se, influence) = (at(&diagnostics.estimates), at(&diagnostics.ses), at(&diagnostics.influence));
+        assert!((estimate - rest).abs() < 0.02, "{estimate} vs {rest}");
+        assert!(se < diagnostics.se / 3.0);
+        assert!((influence / (10.0 - mean) - 1.0).abs() < 0.1, "{influence}");
+    }
+}
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+use num_traits::{Float, FromPrimitive};
+use rand::Rng;
+use rand::distributions::Distribution;
+use statrs::distribution::Beta;
+use crate::{CDF, Interval, QuantileInterval, ReIter, Sample, SamplingMode, SubsampleStrategy, Variance, Weighted};
+
+use super::Statistic;
+
+/// Bag of Little Bootstraps (Kleiner, Talwalkar, Sarkar & Jordan, 2014).
+///
+/// Draws `s` subsets of size `b = ⌈n^γ⌉` without replacement; inside each
+/// subset, `r` resamples of the full size `n` are represented by multinomial
+/// counts over the `b` points, so the statistic only ever sees `b` distinct
+/// observations (as a [`Weighted`] sample). Per-subset standard errors or
+/// percentile intervals are averaged.
+///
+/// Memory and work per resample scale with `b` instead of `n`, which keeps
+/// bootstrap inference practical for millions of rows. Requires a weight-aware
+/// statistic (`Mean`, `Variance`, `Skewness`, `Kurtosis`, …).
+///
+/// The output type selects the inference: `T` for the standard error,
+/// `Interval<T>` for the averaged percentile interval.
+#[derive(Debug, Clone)]
+pub struct BagOfLittleBootstraps<Stat, R> {
+    statistic: Stat,
+    rng: R,
+    subsets: usize,
+    resamples: usize,
+    gamma: f64,
+    confidence: f64,
+}
+
+impl<Stat, R: Rng> BagOfLittleBootstraps<Stat, R> {
+    /// `subsets` subsets (s) with `resamples` resamples (r) each; `γ = 0.7`, 95% intervals.
+    pub fn new(statistic: Stat, rng: R, subsets: usize, resamples: usize) -> Self {
+        Self {
+            statistic,
+            rng,
+            subsets,
+            resamples,
+            gamma: 0.7,
+            confidence: 0.95,
+        }
+    }
+
+    /// Subset size exponent `γ ∈ (0.5, 1]`, `b = ⌈n^γ⌉`
+    pub fn with_gamma(mut self, gamma: f64) -> Self {
+        assert!(gamma > 0.5 && gamma <= 1.0, "γ must be in (0.5, 1]");
+        self.gamma = gamma;
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        debug_assert!((0.0..1.0).contains(&confidence));
+        self.confidence = confidence;
+        self
+    }
+
+    /// Subset size `b` for `n` observations
+    pub fn subset_size(&self, n: usize) -> usize {
+        ((n as f64).powf(self.gamma).ceil() as usize).clamp(1, n.max(1))
+    }
+}
+
+impl<Stat, R: Rng + Clone> BagOfLittleBootstraps<Stat, R> {
+    /// Replicates `θ*` of every subset.
+    fn replicates<T>(&self, data: &Sample<T>) -> Vec<Vec<T>>
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<Weighted<T>, T>,
+    {
+        let n = data.len();
+        let b = self.subset_size(n);
+        let mut rng = self.rng.clone();
+
+        let subsets: Vec<Sample<T>> =
+            ReIter::new(SubsampleStrategy::new(&mut rng, b, SamplingMode::WithoutReplacement), data)
+                .take(self.subsets)
+                .collect();
+
+        subsets
+            .into_iter()
+            .map(|subset| {
+                let points: Arc<[T]> = subset.data.into();
+                (0..self.resamples)
+                    .map(|_| {
+                        let weights = multinomial_counts(&mut rng, n as u64, points.len());
+                        let resample = Weighted {
+                            data: Arc::clone(&points),
+                            weights,
+                        };
+                        self.statistic.compute(&resample)
+                    })
+                    .filter(|theta| !theta.is_nan())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Mean of the defined values.
+fn average<T: Float + FromPrimitive>(values: impl Iterator<Item = T>) -> T {
+    let (sum, count) = values
+        .filter(|v| !v.is_nan())
+        .fold((T::zero(), 0), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        T::nan()
+    } else {
+        sum / T::from_usize(count).expect("usize fits in float")
+    }
+}
+
+/// Averaged standard error `(1/s) Σⱼ SE*ⱼ`
+impl<T, Stat, R> Statistic<Sample<T>, T> for BagOfLittleBootstraps<Stat, R>
+where
+    T: Float + FromPrimitive,
+    Stat: Statistic<Weighted<T>, T>,
+    R: Rng + Clone,
+{
+    fn compute(&self, data: &Sample<T>) -> T {
+        if data.len() < 2 {
+            return T::nan();
+        }
+        average(
+            self.replicates(data)
+                .iter()
+                .map(|thetas| Variance::default().compute(thetas).sqrt()),
+        )
+    }
+}
+
+/// Averaged percentile interval `[(1/s) Σⱼ θ*ⱼ₍α/₂₎, (1/s) Σⱼ θ*ⱼ₍₁₋α/₂₎]`
+impl<T, Stat, R> Statistic<Sample<T>, Interval<T>> for BagOfLittleBootstraps<Stat, R>
+where
+    T: Float + FromPrimitive,
+    Stat: Statistic<Weighted<T>, T> + Statistic<Sample<T>, T>,
+    R: Rng + Clone,
+{
+    fn compute(&self, data: &Sample<T>) -> Interval<T> {
+        if data.len() < 2 {
+            return Interval::nan();
+        }
+
+        let bounds: Vec<(T, T)> = self
+            .replicates(data)
+            .iter()
+            .filter(|thetas| thetas.len() >= 2)
+            .map(|thetas| QuantileInterval::percentile(self.confidence).compute(&CDF.compute(thetas)))
+            .collect();
+        if bounds.is_empty() {
+            return Interval::nan();
+        }
+
+        let lower = average(bounds.iter().map(|bound| bound.0));
+        let upper = average(bounds.iter().map(|bound| bound.1));
+        let theta_hat: T = self.statistic.compute(data);
+
+        Interval::new(lower, upper)
+            .estimate(theta_hat)
+            .confidence(self.confidence)
+    }
+}
+
+/// Multinomial(n; 1/b, …, 1/b) counts via sequential conditional binomials.
+fn multinomial_counts<T: FromPrimitive, R: Rng + ?Sized>(rng: &mut R, n: u64, b: usize) -> Vec<T> {
+    let mut remaining = n;
+    (0..b)
+        .map(|cell| {
+            let count = binomial(rng, remaining, 1.0 / (b - cell) as f64);
+            remaining -= count;
+            T::from_u64(count).expect("count fits in float")
+        })
+        .collect()
+}
+
+/// Binomial(n, p) draw in `O(log n)` expected time.
+///
+/// Devroye's (1986) recursion on the Beta-distributed order statistics of the
+/// underlying uniforms shrinks `n` until the mean is small, then the remainder
+/// is drawn by CDF inversion.
+fn binomial<R: Rng + ?Sized>(rng: &mut R, n: u64, p: f64) -> u64 {
+    if p > 0.5 {
+        return n - binomial(rng, n, 1.0 - p);
+    }
+
+    let (mut n, mut p, mut count) = (n, p, 0);
+    while n as f64 * p > 30.0 {
+        // U₍ᵢ₎ ~ Beta(i, n + 1 - i) is the i-th smallest of n uniforms
+        let i = n / 2 + 1;
+        let x = Beta::new(i as f64, (n + 1 - i) as f64)
+            .expect("positive shapes")
+            .sample(rng);
+        if x <= p {
+            count += i;
+            n -= i;
+            p = (p - x) / (1.0 - x);
+        } else {
+            n = i - 1;
+            p /= x;
+        }
+    }
+
+    count + binomial_inversion(rng, n, p)
+}
+
+/// Binomial(n, p) draw walking the pmf recurrence from zero; `O(n·min(p, 1-p))`.
+fn binomial_inversion<R: Rng + ?Sized>(rng: &mut R, n: u64, p: f64) -> u64 {
+    if p > 0.5 {
+        return n - binomial_inversion(rng, n, 1.0 - p);
+    }
+
+    let q = 1.0 - p;
+    let ratio = p / q;
+    let mut pmf = q.powf(n as f64);
+    let mut cdf = pmf;
+    let u: f64 = rng.r#gen();
+    let mut k = 0;
+    while u > cdf && k < n {
+        pmf *= ratio * (n - k) as f64 / (k + 1) as f64;
+        k += 1;
+        cdf += pmf;
+    }
+    k
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mean, SEMean};
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    #[test]
+    fn binomial_moments() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+        let (n, p) = (10_000, 0.3);
+        let draws: Vec<f64> = (0..4000).map(|_| binomial(&mut rng, n, p) as f64).collect();
+        let mean: f64 = Mean.compute(&draws);
+        let variance: f64 = Variance::default().compute(&draws);
+        assert!((mean / 3000.0 - 1.0).abs() < 0.005, "{mean}");
+        assert!((variance / 2100.0 - 1.0).abs() < 0.1, "{variance}");
+
+        let counts: Vec<f64> = multinomial_counts(&mut rng, 1000, 7);
+        assert!((counts.iter().sum::<f64>() - 1000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn little_bootstraps_reproduce_mean_se() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(9);
+        let sample: Sample<f64> = (0..20_000).map(|_| rng.r#gen::<f64>()).collect();
+        let blb = BagOfLittleBootstraps::new(Mean, Xoshiro256PlusPlus::seed_from_u64(2), 10, 100);
+        assert_eq!(blb.subset_size(sample.len()), 1025);
+
+        let se: f64 = blb.compute(&sample);
+        let textbook: f64 = SEMean::default().compute(&sample);
+        assert!((se / textbook - 1.0).abs() < 0.1, "{se} vs {textbook}");
+
+        let interval: Interval<f64> = blb.compute(&sample);
+        assert!(interval.contains(&0.5), "{interval:?}");
+        assert!((interval.width() / (2.0 * 1.96 * textbook) - 1.0).abs() < 0.15);
+    }
+}
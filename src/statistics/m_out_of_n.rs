@@ -0,0 +1,193 @@
+use num_traits::{Float, FromPrimitive};
+use rand::{Rng, SeedableRng};
+use crate::{CDF, ConvergenceRate, EmpiricalCDF, Interval, QuantileInterval, Re, Sample, Subsample};
+
+use super::Statistic;
+
+/// m-out-of-n bootstrap interval with a data-driven choice of m.
+///
+/// Resamples `m < n` observations with replacement, which keeps the bootstrap
+/// consistent for extremes and boundary parameters (max, min, …). The roots
+/// `τₘ(θ̂*ₘ - θ̂ₙ)` are rescaled to the full sample by the convergence rate:
+/// ```text
+/// [θ̂ₙ - r*₍₁₋α/₂₎/τₙ, θ̂ₙ - r*₍α/₂₎/τₙ]
+/// ```
+/// Unless fixed with [`with_size`](Self::with_size), m is chosen by the
+/// Bickel–Sakov (2008) rule: among `mⱼ = ⌈qʲn⌉` pick the one whose root
+/// distribution is closest (Kolmogorov distance) to that of `mⱼ₊₁`.
+#[derive(Debug, Clone)]
+pub struct MOutOfNBootstrap<Stat, R> {
+    statistic: Stat,
+    rng: R,
+    samples: usize,
+    confidence: f64,
+    rate: ConvergenceRate,
+    /// Geometric spacing `q` of the candidate sizes
+    spacing: f64,
+    size: Option<usize>,
+}
+
+impl<Stat, R: Rng> MOutOfNBootstrap<Stat, R> {
+    pub fn new(statistic: Stat, rng: R, samples: usize, confidence: f64) -> Self {
+        debug_assert!((0.0..1.0).contains(&confidence));
+        Self {
+            statistic,
+            rng,
+            samples,
+            confidence,
+            rate: ConvergenceRate::default(),
+            spacing: 0.75,
+            size: None,
+        }
+    }
+
+    /// Convergence rate of the statistic (default `√n`)
+    pub fn with_rate(mut self, rate: ConvergenceRate) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    /// Spacing `q ∈ (0, 1)` of the Bickel–Sakov candidates `⌈qʲn⌉` (default 0.75)
+    pub fn with_spacing(mut self, spacing: f64) -> Self {
+        assert!(spacing > 0.0 && spacing < 1.0, "Spacing must be in (0, 1)");
+        self.spacing = spacing;
+        self
+    }
+
+    /// Fix m instead of selecting it from the data
+    pub fn with_size(mut self, m: usize) -> Self {
+        assert!(m > 0, "Resample size must be positive");
+        self.size = Some(m);
+        self
+    }
+}
+
+impl<Stat, R: Rng + SeedableRng + Clone> MOutOfNBootstrap<Stat, R> {
+    /// Bootstrap roots `τₘ(θ̂*ₘ - θ̂ₙ)` for resamples of size `m`.
+    fn roots<T>(&self, data: &Sample<T>, theta_hat: T, m: usize) -> Vec<T>
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<Sample<T>, T>,
+    {
+        let tau = T::from_f64(self.rate.tau(m)).expect("rate fits in float");
+        Subsample::m_out_of_n(self.rng.clone(), m)
+            .re(data)
+            .take(self.samples)
+            .map(|resample| (self.statistic.compute(&resample) - theta_hat) * tau)
+            .filter(|root| !root.is_nan())
+            .collect()
+    }
+
+    /// Resample size `m`: the fixed one, or the Bickel–Sakov choice.
+    pub fn select_m<T>(&self, data: &Sample<T>) -> usize
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<Sample<T>, T>,
+    {
+        if let Some(m) = self.size {
+            return m;
+        }
+
+        let n = data.len();
+        let theta_hat = self.statistic.compute(data);
+
+        // Candidates n = m₀ > m₁ > … ≥ 2, strictly decreasing
+        let mut candidates = vec![n];
+        let mut m = n as f64;
+        loop {
+            m *= self.spacing;
+            let next = m.ceil() as usize;
+            if next < 2 {
+                break;
+            }
+            if candidates.last().is_some_and(|&last| next < last) {
+                candidates.push(next);
+            }
+        }
+
+        let distributions: Vec<EmpiricalCDF<T>> = candidates
+            .iter()
+            .map(|&m| CDF.compute(&self.roots(data, theta_hat, m)))
+            .collect();
+
+        candidates
+            .iter()
+            .zip(distributions.windows(2))
+            .map(|(&m, pair)| match pair {
+                [current, next] => (m, kolmogorov_distance(current, next)),
+                _ => (m, f64::INFINITY),
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(n, |(m, _)| m)
+    }
+}
+
+/// `sup |F(x) - G(x)|` over the jump points of both distributions.
+fn kolmogorov_distance<T: Float>(f: &EmpiricalCDF<T>, g: &EmpiricalCDF<T>) -> f64 {
+    f.points()
+        .iter()
+        .chain(g.points())
+        .map(|x| (f.eval_float(x) - g.eval_float(x)).abs())
+        .filter(|d| !d.is_nan())
+        .fold(0.0, f64::max)
+}
+
+impl<T, Stat, R> Statistic<Sample<T>, Interval<T>> for MOutOfNBootstrap<Stat, R>
+where
+    T: Float + FromPrimitive,
+    Stat: Statistic<Sample<T>, T>,
+    R: Rng + SeedableRng + Clone,
+{
+    fn compute(&self, data: &Sample<T>) -> Interval<T> {
+        if data.len() < 2 {
+            return Interval::nan();
+        }
+
+        let theta_hat = self.statistic.compute(data);
+        let m = self.select_m(data);
+        let roots = self.roots(data, theta_hat, m);
+        if roots.len() < 2 {
+            return Interval::nan();
+        }
+
+        let ecdf = CDF.compute(&roots);
+        let (r_lower, r_upper) = QuantileInterval::percentile(self.confidence).compute(&ecdf);
+        let tau_n = T::from_f64(self.rate.tau(data.len())).expect("rate fits in float");
+
+        Interval::new(theta_hat - r_upper / tau_n, theta_hat - r_lower / tau_n)
+            .estimate(theta_hat)
+            .confidence(self.confidence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    /// Sample maximum
+    struct Max;
+
+    impl Statistic<Sample<f64>, f64> for Max {
+        fn compute(&self, data: &Sample<f64>) -> f64 {
+            data.data.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+        }
+    }
+
+    #[test]
+    fn covers_uniform_endpoint_with_linear_rate() {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(7);
+        let sample: Sample<f64> = (0..400).map(|_| rng.r#gen::<f64>()).collect();
+
+        let estimator = MOutOfNBootstrap::new(Max, Xoshiro256PlusPlus::seed_from_u64(1), 1000, 0.95)
+            .with_rate(ConvergenceRate::linear());
+        let m = estimator.select_m(&sample);
+        assert!(m < sample.len(), "selected m = {m}");
+
+        let interval = estimator.compute(&sample);
+        let max = Max.compute(&sample);
+        assert!(interval.contains(&1.0), "m = {m}: {interval:?}");
+        assert!(interval.upper > max);
+    }
+}
@@ -1,6 +1,7 @@
-use num_traits::Float;
+use num_traits::{Float, FromPrimitive};
 
 use crate::math::Vector;
+use crate::Weighted;
 use super::Statistic;
 
 /// Computes component-wise arithmetic mean using **Kahan summation** to
@@ -40,6 +41,26 @@ where
     }
 }
 
+/// Weighted mean `Σwᵢxᵢ / Σwᵢ`, Kahan-summed like the unweighted one.
+impl<T> Statistic<Weighted<T>, T> for Mean
+where
+    T: Float + FromPrimitive,
+{
+    fn compute(&self, data: &Weighted<T>) -> T {
+        let mut sum = T::zero();
+        let mut c = T::zero();
+
+        for (&x, &w) in data.iter() {
+            let y = w * x - c;
+            let t = sum + y;
+            c = (t - sum) - y;
+            sum = t;
+        }
+
+        sum / data.total()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
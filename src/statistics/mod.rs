@@ -43,6 +43,15 @@ mod cdf;
 mod studentized_bootstrap;
 mod quantile;
 mod ci;
+mod rate;
+mod m_out_of_n;
+mod subsampling_interval;
+mod accumulator;
+mod little_bootstraps;
+mod double_bootstrap;
+mod jackknife_after_bootstrap;
+mod conformal;
+mod adaptive;
 
 
 pub use mean::Mean;
@@ -51,10 +60,19 @@ pub use basic::*;
 use num_traits::{Float, FromPrimitive};
 pub use se::{SEMean, SE};
 pub use studentized::Studentized;
-pub use cdf::{CDF, EmpiricalCDF};
+pub use cdf::{CDF, EmpiricalCDF, WeightedEmpiricalCDF};
 pub use quantile::{Quantile, QuantileInterval};
 pub use studentized_bootstrap::StudentizedBootstrap;
 pub use ci::{Interval, IntervalStyle};
+pub use rate::ConvergenceRate;
+pub use m_out_of_n::MOutOfNBootstrap;
+pub use subsampling_interval::SubsamplingInterval;
+pub use accumulator::{Accumulator, MeanAccumulator, Mergeable, VarianceAccumulator};
+pub use little_bootstraps::BagOfLittleBootstraps;
+pub use double_bootstrap::{DoubleBootstrap, PercentilePivot, Pivot, StudentizedPivot};
+pub use jackknife_after_bootstrap::{Influence, JackknifeAfterBootstrap};
+pub use conformal::{ConformalBand, JackknifePlus, JackknifePlusBand, SplitConformal};
+pub use adaptive::{Adaptive, AdaptiveResult, AdaptiveStop, AdaptiveTarget};
 
 // ===== 0-tuple: Identity statistic (no-op) =====
 impl<D> Statistic<D, ()> for () {
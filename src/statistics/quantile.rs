@@ -1,5 +1,6 @@
 use super::Statistic;
-use crate::EmpiricalCDF;
+use num_traits::Float;
+use crate::{EmpiricalCDF, WeightedEmpiricalCDF};
 
 /// Discrete quantile estimator (inverse ECDF).
 ///
@@ -50,8 +51,19 @@ impl<T: Clone> Statistic<EmpiricalCDF<T>, T> for Quantile {
             .saturating_sub(1)
             .min(n - 1);
 
-        // Safe: idx guaranteed in [0, n-1] by clamping above
-        ecdf.points()[idx].clone()
+        ecdf.points()
+            .get(idx)
+            .cloned()
+            .expect("idx is clamped to [0, n-1]")
+    }
+}
+
+impl<T: Float> Statistic<WeightedEmpiricalCDF<T>, T> for Quantile {
+    /// Weighted type-1 quantile; multinomial counts reproduce the quantile of
+    /// the copied sample.
+    #[inline]
+    fn compute(&self, ecdf: &WeightedEmpiricalCDF<T>) -> T {
+        ecdf.inverse(self.p)
     }
 }
 
@@ -97,8 +109,15 @@ impl<T: Clone> Statistic<EmpiricalCDF<T>, (T, T)> for QuantileInterval {
             .saturating_sub(1)
             .min(n - 1);
 
-        // Safe: indices clamped to valid range
         let points = ecdf.points();
-        (points[idx_low].clone(), points[idx_up].clone())
+        let at = |idx: usize| points.get(idx).cloned().expect("indices are clamped to [0, n-1]");
+        (at(idx_low), at(idx_up))
+    }
+}
+
+impl<T: Float> Statistic<WeightedEmpiricalCDF<T>, (T, T)> for QuantileInterval {
+    #[inline]
+    fn compute(&self, ecdf: &WeightedEmpiricalCDF<T>) -> (T, T) {
+        (ecdf.inverse(self.lower), ecdf.inverse(self.upper))
     }
 }
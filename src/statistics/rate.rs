@@ -0,0 +1,38 @@
+/// Convergence rate `τₙ = n^β` of an estimator: `τₙ(θ̂ₙ - θ)` has a
+/// non-degenerate limit law.
+///
+/// β = 1/2 for smooth statistics, β = 1 for extremes of distributions with a
+/// jump at the boundary (e.g. the maximum of a uniform sample).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvergenceRate {
+    pub exponent: f64,
+}
+
+impl ConvergenceRate {
+    pub fn new(exponent: f64) -> Self {
+        assert!(exponent > 0.0, "Convergence rate exponent must be positive");
+        Self { exponent }
+    }
+
+    /// `τₙ = √n`
+    pub fn sqrt() -> Self {
+        Self { exponent: 0.5 }
+    }
+
+    /// `τₙ = n`
+    pub fn linear() -> Self {
+        Self { exponent: 1.0 }
+    }
+
+    /// `τₙ` for a sample of size `n`
+    #[inline]
+    pub fn tau(&self, n: usize) -> f64 {
+        (n as f64).powf(self.exponent)
+    }
+}
+
+impl Default for ConvergenceRate {
+    fn default() -> Self {
+        Self::sqrt()
+    }
+}
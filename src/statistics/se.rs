@@ -1,5 +1,7 @@
 use num_traits::{Float, FromPrimitive};
-use crate::{Jackknife, Re, Variance};
+use crate::{Jackknife, Re, ReLend, Sample, Stream, Variance};
+#[cfg(feature = "rayon")]
+use crate::RePar;
 use super::Statistic;
 
 
@@ -74,6 +76,10 @@ where
 /// SE_boot(θ̂) = std({ θ̂*(b) | b = 1..B })
 /// ```
 /// where θ̂*(b) is the statistic computed on the b-th resample.
+///
+/// The spread is scaled by the resampler's `variance_scale`: `1/(B-1)` for
+/// Monte Carlo resamplers, `(n-1)/n` for the leave-one-out jackknife,
+/// `(n-d)/(d·B)` for delete-d and `(g-1)/g` for the grouped jackknife.
 #[derive(Debug, Clone, Copy)]
 pub struct SE<Stat, Resampler> {
     statistic: Stat,
@@ -109,10 +115,9 @@ impl<Stat, Resampler> SE<Stat, Resampler> {
 
 impl<D, T, Stat, Resampler> Statistic<D, T> for SE<Stat, Resampler>
 where
-    D: AsRef<[T]>,
     T: Float + FromPrimitive,
-    Resampler: Re<D, Item = D>,
-    Stat: Statistic<D, T>,
+    Resampler: Re<D>,
+    Stat: Statistic<Resampler::Item, T>,
 {
     fn compute(&self, data: &D) -> T {
         let estimates: Vec<T> = self
@@ -122,8 +127,76 @@ where
             .map(|resample| self.statistic.compute(&resample))
             .collect();
 
-        Variance::default()
-            .compute(&estimates)
-            .sqrt()
+        spread(&estimates, self.resampler.variance_scale(data, estimates.len()))
+    }
+}
+
+impl<Stat, Resampler> SE<Stat, Resampler> {
+    /// Buffer-reusing counterpart of `compute`: every replicate is lent through
+    /// `for_each_resample`, so only the vector of estimates is allocated.
+    pub fn compute_buffered<E, T>(&self, data: &Sample<E>) -> T
+    where
+        T: Float + FromPrimitive,
+        Resampler: ReLend<Sample<E>, Elem = E>,
+        Stat: Statistic<Sample<E>, T>,
+    {
+        let mut estimates: Vec<T> = Vec::new();
+        self.resampler
+            .for_each_resample(data, self.samples, |resample| {
+                estimates.push(self.statistic.compute(resample));
+            });
+
+        spread(&estimates, self.resampler.variance_scale(data, estimates.len()))
     }
 }
+
+#[cfg(feature = "rayon")]
+impl<Stat, Resampler> SE<Stat, Resampler> {
+    /// Parallel counterpart of `compute`: replicates are drawn with `re_par`,
+    /// so the result does not depend on the number of threads.
+    pub fn compute_par<D, T>(&self, data: &D) -> T
+    where
+        D: Sync,
+        T: Float + FromPrimitive + Send,
+        Resampler: RePar<D> + Sync,
+        Resampler::Item: Send,
+        Stat: Statistic<Resampler::Item, T> + Sync,
+    {
+        use rayon::prelude::*;
+
+        let estimates: Vec<T> = self
+            .resampler
+            .re_par(data)
+            .take(self.samples)
+            .map(|resample| self.statistic.compute(&resample))
+            .collect();
+
+        spread(&estimates, self.resampler.variance_scale(data, estimates.len()))
+    }
+}
+
+impl<Stat: Clone, Resampler: Stream> Stream for SE<Stat, Resampler> {
+    /// The estimator with its resampler on stream `index`, e.g. for the
+    /// inner SE of the `index`-th outer replicate.
+    fn stream(&self, index: usize) -> Self {
+        Self {
+            statistic: self.statistic.clone(),
+            resampler: self.resampler.stream(index),
+            samples: self.samples,
+        }
+    }
+
+    fn with_seed(self, seed: u64) -> Self {
+        Self {
+            resampler: self.resampler.with_seed(seed),
+            ..self
+        }
+    }
+}
+
+/// `sqrt(scale · Σ(θ*ᵢ - θ̄*)²)`; NaN for fewer than two replicates.
+fn spread<T: Float + FromPrimitive>(estimates: &[T], scale: f64) -> T {
+    let n = T::from_usize(estimates.len()).expect("usize fits in float");
+    let scale = T::from_f64(scale).expect("scale fits in float");
+    (Variance::new(0).compute(&estimates) * n * scale).sqrt()
+}
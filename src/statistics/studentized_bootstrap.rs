@@ -1,8 +1,15 @@
 use num_traits::{Float, FromPrimitive, float::TotalOrder};
-use crate::{CDF, Interval, QuantileInterval, Re, SE};
+use crate::{CDF, Interval, QuantileInterval, Re, ReLend, Sample, SE, Stream};
+#[cfg(feature = "rayon")]
+use crate::RePar;
 
 use super::Statistic;
 
+/// Studentized (bootstrap-t) interval.
+///
+/// Every outer replicate is standardized by its own inner SE estimate. The
+/// `i`-th outer replicate uses stream `i` of the inner resampler (see
+/// [`Stream`]), so no two outer replicates share their inner draws.
 #[derive(Debug, Clone)]
 pub struct StudentizedBootstrap<Stat, InnerResampler, OuterResampler> {
     statistic: Stat,
@@ -34,10 +41,9 @@ impl<Stat, InnerResampler, OuterResampler> StudentizedBootstrap<Stat, InnerResam
 impl<D, T, Stat, InnerResampler, OuterResampler> Statistic<D, Interval<T>>
     for StudentizedBootstrap<Stat, InnerResampler, OuterResampler>
 where
-    D: AsRef<[T]>,
     T: Float + FromPrimitive,
-    Stat: Statistic<D, T>,
-    InnerResampler: Re<D, Item = D>,
+    Stat: Statistic<D, T> + Clone,
+    InnerResampler: Re<D, Item = D> + Stream,
     OuterResampler: Re<D, Item = D>,
 {
     fn compute(&self, data: &D) -> Interval<T> {
@@ -50,18 +56,32 @@ where
         let t_star: Vec<T> = self.resampler
             .re(data)
             .take(self.samples)
-            .filter_map(|resample| {
-                let theta_star = self.statistic.compute(&resample);
-                let se_theta_star = self.se.compute(&resample);
-
-                if se_theta_star.is_zero() || theta_star.is_nan() || se_theta_star.is_nan() {
-                    None
-                } else {
-                    Some((theta_star - theta_hat) / se_theta_star)
-                }
-            })
+            .enumerate()
+            .filter_map(|(index, resample)| self.t_statistic(index, &resample, theta_hat))
             .collect();
 
+        self.interval(theta_hat, se_theta_hat, &t_star)
+    }
+}
+
+impl<Stat, InnerResampler, OuterResampler> StudentizedBootstrap<Stat, InnerResampler, OuterResampler> {
+    /// Studentized `index`-th replicate `t* = (θ̂* - θ̂) / SE*`, `None` when undefined.
+    fn t_statistic<D, T>(&self, index: usize, resample: &D, theta_hat: T) -> Option<T>
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<D, T> + Clone,
+        InnerResampler: Re<D, Item = D> + Stream,
+    {
+        let theta_star = self.statistic.compute(resample);
+        let se_theta_star = self.se.stream(index).compute(resample);
+        studentize(theta_star, se_theta_star, theta_hat)
+    }
+
+    /// Invert the studentized replicates into `[θ̂ - t*₍₁₋α/₂₎·SE, θ̂ - t*₍α/₂₎·SE]`.
+    fn interval<T>(&self, theta_hat: T, se_theta_hat: T, t_star: &[T]) -> Interval<T>
+    where
+        T: Float + FromPrimitive,
+    {
         if t_star.len() < 2 {
             return Interval::nan();
         }
@@ -78,6 +98,71 @@ where
             .estimate(theta_hat)
             .confidence(self.confidence)
     }
+
+    /// Buffer-reusing counterpart of `compute`: outer and inner replicates are
+    /// lent through `for_each_resample` instead of being allocated one by one.
+    pub fn compute_buffered<E, T>(&self, data: &Sample<E>) -> Interval<T>
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<Sample<E>, T> + Clone,
+        InnerResampler: ReLend<Sample<E>, Elem = E> + Stream,
+        OuterResampler: ReLend<Sample<E>, Elem = E>,
+    {
+        let theta_hat = self.statistic.compute(data);
+        let se_theta_hat = self.se.compute_buffered(data);
+        if se_theta_hat.is_nan() || se_theta_hat.is_zero() {
+            return Interval::nan();
+        }
+
+        let mut t_star: Vec<T> = Vec::new();
+        let mut index = 0;
+        self.resampler.for_each_resample(data, self.samples, |resample| {
+            let theta_star = self.statistic.compute(resample);
+            let se_theta_star = self.se.stream(index).compute_buffered(resample);
+            t_star.extend(studentize(theta_star, se_theta_star, theta_hat));
+            index += 1;
+        });
+
+        self.interval(theta_hat, se_theta_hat, &t_star)
+    }
+
+    /// Parallel counterpart of `compute`: outer replicates are drawn with `re_par`,
+    /// so the interval does not depend on the number of threads.
+    #[cfg(feature = "rayon")]
+    pub fn compute_par<D, T>(&self, data: &D) -> Interval<T>
+    where
+        D: Send + Sync,
+        T: Float + FromPrimitive + Send + Sync,
+        Stat: Statistic<D, T> + Clone + Sync,
+        InnerResampler: Re<D, Item = D> + Stream + Sync,
+        OuterResampler: RePar<D, Item = D> + Sync,
+    {
+        use rayon::prelude::*;
+
+        let theta_hat = self.statistic.compute(data);
+        let se_theta_hat = self.se.compute(data);
+        if se_theta_hat.is_nan() || se_theta_hat.is_zero() {
+            return Interval::nan();
+        }
+
+        let t_star: Vec<T> = self.resampler
+            .re_par(data)
+            .take(self.samples)
+            .enumerate()
+            .filter_map(|(index, resample)| self.t_statistic(index, &resample, theta_hat))
+            .collect();
+
+        self.interval(theta_hat, se_theta_hat, &t_star)
+    }
+}
+
+/// `(θ̂* - θ̂) / SE*`, `None` when undefined.
+fn studentize<T: Float>(theta_star: T, se_theta_star: T, theta_hat: T) -> Option<T> {
+    if se_theta_star.is_zero() || theta_star.is_nan() || se_theta_star.is_nan() {
+        None
+    } else {
+        Some((theta_star - theta_hat) / se_theta_star)
+    }
 }
 
 // // Convenience constructors for common use cases
@@ -0,0 +1,210 @@
+use num_traits::{Float, FromPrimitive};
+use rand::{Rng, SeedableRng};
+use crate::{CDF, ConvergenceRate, Interval, Quantile, QuantileInterval, Re, Sample, Subsample, fixed_size_policy};
+
+use super::Statistic;
+
+/// Quantile pairs `(t, 1 - t)` whose ranges feed the rate estimate
+const RATE_QUANTILES: [f64; 4] = [0.05, 0.1, 0.15, 0.2];
+
+/// Exponents γ of the subsample sizes `nᵞ` used to estimate the rate
+const RATE_SIZES: [f64; 5] = [0.4, 0.5, 0.6, 0.7, 0.8];
+
+/// Subsampling confidence interval (Politis, Romano & Wolf, 1999).
+///
+/// Draws subsamples of size `b ≪ n` without replacement and approximates the
+/// law of `τₙ(θ̂ₙ - θ)` by that of `τ_b(θ̂_b - θ̂ₙ)`:
+/// ```text
+/// [θ̂ₙ - c*₍₁₋α/₂₎/τₙ, θ̂ₙ - c*₍α/₂₎/τₙ]
+/// ```
+/// Valid whenever `τₙ(θ̂ₙ - θ)` has a limit law, `b → ∞` and `b/n → 0`, which
+/// covers many cases where the bootstrap fails. When the rate `τₙ = n^β` is
+/// unknown it is estimated from several subsample sizes (see
+/// [`estimate_rate`](Self::estimate_rate)).
+#[derive(Debug, Clone)]
+pub struct SubsamplingInterval<Stat, R> {
+    statistic: Stat,
+    rng: R,
+    samples: usize,
+    confidence: f64,
+    rate: Option<ConvergenceRate>,
+    size: Option<usize>,
+}
+
+impl<Stat, R: Rng> SubsamplingInterval<Stat, R> {
+    /// Interval with estimated rate and subsample size `⌈n^{2/3}⌉`.
+    pub fn new(statistic: Stat, rng: R, samples: usize, confidence: f64) -> Self {
+        debug_assert!((0.0..1.0).contains(&confidence));
+        Self {
+            statistic,
+            rng,
+            samples,
+            confidence,
+            rate: None,
+            size: None,
+        }
+    }
+
+    /// Known convergence rate (skips estimation)
+    pub fn with_rate(mut self, rate: ConvergenceRate) -> Self {
+        self.rate = Some(rate);
+        self
+    }
+
+    /// Fixed subsample size `b`
+    pub fn with_size(mut self, b: usize) -> Self {
+        assert!(b > 1, "Subsample size must exceed 1");
+        self.size = Some(b);
+        self
+    }
+
+    fn subsample_size(&self, n: usize) -> usize {
+        self.size
+            .unwrap_or_else(|| (n as f64).powf(2.0 / 3.0).ceil() as usize)
+            .clamp(2, n.saturating_sub(1).max(2))
+    }
+}
+
+impl<Stat, R: Rng + SeedableRng + Clone> SubsamplingInterval<Stat, R> {
+    /// Unnormalized subsample deviations `θ̂_b - θ̂ₙ`.
+    fn deviations<T>(&self, data: &Sample<T>, theta_hat: T, b: usize) -> Vec<T>
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<Sample<T>, T>,
+    {
+        Subsample::with_policy(self.rng.clone(), fixed_size_policy(b))
+            .re(data)
+            .take(self.samples)
+            .map(|subsample| self.statistic.compute(&subsample) - theta_hat)
+            .filter(|deviation| !deviation.is_nan())
+            .collect()
+    }
+
+    /// Estimate `β` in `τₙ = n^β` from the shrinkage of subsample quantile ranges.
+    ///
+    /// The range `q_b(1-t) - q_b(t)` of `θ̂_b - θ̂ₙ` behaves like `C·b^{-β}`, so
+    /// `-β` is the least-squares slope of the averaged log-ranges against `log b`
+    /// over `b = n^0.4, …, n^0.8` (Bertail, Politis & Romano, 1999).
+    pub fn estimate_rate<T>(&self, data: &Sample<T>) -> ConvergenceRate
+    where
+        T: Float + FromPrimitive,
+        Stat: Statistic<Sample<T>, T>,
+    {
+        let n = data.len();
+        let theta_hat = self.statistic.compute(data);
+
+        let points: Vec<(f64, f64)> = RATE_SIZES
+            .iter()
+            .map(|gamma| ((n as f64).powf(*gamma).ceil() as usize).clamp(2, n.max(2)))
+            .filter_map(|b| {
+                let deviations = self.deviations(data, theta_hat, b);
+                if deviations.is_empty() {
+                    return None;
+                }
+                let ecdf = CDF.compute(&deviations);
+                let log_ranges: Vec<f64> = RATE_QUANTILES
+                    .iter()
+                    .filter_map(|&t| {
+                        let range: T = Quantile::new(1.0 - t).compute(&ecdf) - Quantile::new(t).compute(&ecdf);
+                        range.to_f64().filter(|r| *r > 0.0).map(f64::ln)
+                    })
+                    .collect();
+                (!log_ranges.is_empty()).then(|| {
+                    let mean = log_ranges.iter().sum::<f64>() / log_ranges.len() as f64;
+                    ((b as f64).ln(), mean)
+                })
+            })
+            .collect();
+
+        let k = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / k;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / k;
+        let sxy: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+        let beta = -sxy / sxx;
+        if beta.is_finite() && beta > 0.0 {
+            ConvergenceRate::new(beta)
+        } else {
+            // Degenerate ranges: fall back to the regular rate
+            ConvergenceRate::default()
+        }
+    }
+}
+
+impl<T, Stat, R> Statistic<Sample<T>, Interval<T>> for SubsamplingInterval<Stat, R>
+where
+    T: Float + FromPrimitive,
+    Stat: Statistic<Sample<T>, T>,
+    R: Rng + SeedableRng + Clone,
+{
+    fn compute(&self, data: &Sample<T>) -> Interval<T> {
+        let n = data.len();
+        if n < 3 {
+            return Interval::nan();
+        }
+
+        let theta_hat = self.statistic.compute(data);
+        let rate = self.rate.unwrap_or_else(|| self.estimate_rate(data));
+        let b = self.subsample_size(n);
+
+        let deviations = self.deviations(data, theta_hat, b);
+        if deviations.len() < 2 {
+            return Interval::nan();
+        }
+
+        let tau_b = T::from_f64(rate.tau(b)).expect("rate fits in float");
+        let tau_n = T::from_f64(rate.tau(n)).expect("rate fits in float");
+        let roots: Vec<T> = deviations.into_iter().map(|d| d * tau_b).collect();
+
+        let ecdf = CDF.compute(&roots);
+        let (c_lower, c_upper) = QuantileInterval::percentile(self.confidence).compute(&ecdf);
+
+        Interval::new(theta_hat - c_upper / tau_n, theta_hat - c_lower / tau_n)
+            .estimate(theta_hat)
+            .confidence(self.confidence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mean;
+    use rand::SeedableRng;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    struct Max;
+
+    impl Statistic<Sample<f64>, f64> for Max {
+        fn compute(&self, data: &Sample<f64>) -> f64 {
+            data.data.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+        }
+    }
+
+    fn uniform(n: usize, seed: u64) -> Sample<f64> {
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+        (0..n).map(|_| rng.r#gen::<f64>()).collect()
+    }
+
+    #[test]
+    fn estimates_root_n_and_n_rates() {
+        let sample = uniform(4000, 21);
+        let rng = Xoshiro256PlusPlus::seed_from_u64(3);
+
+        let mean_rate = SubsamplingInterval::new(Mean, rng.clone(), 400, 0.95).estimate_rate(&sample);
+        assert!((mean_rate.exponent - 0.5).abs() < 0.15, "{mean_rate:?}");
+
+        let max_rate = SubsamplingInterval::new(Max, rng, 400, 0.95).estimate_rate(&sample);
+        assert!((max_rate.exponent - 1.0).abs() < 0.25, "{max_rate:?}");
+    }
+
+    #[test]
+    fn interval_covers_population_mean() {
+        let sample = uniform(1000, 5);
+        let interval = SubsamplingInterval::new(Mean, Xoshiro256PlusPlus::seed_from_u64(8), 1000, 0.95)
+            .with_rate(ConvergenceRate::sqrt())
+            .compute(&sample);
+        assert!(interval.contains(&0.5), "{interval:?}");
+        assert!(interval.width() < 0.1);
+    }
+}